@@ -0,0 +1,217 @@
+use std::alloc::{self, GlobalAlloc, Layout};
+
+/// The global memory allocator, used by default for all imposters and collections
+///
+/// This forwards every call to the allocator registered with `#[global_allocator]`,
+/// which is the same allocator used by [`Box`] and [`Vec`].
+/// Conversions between imposters and those types are only available when using this allocator.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Global;
+
+unsafe impl GlobalAlloc for Global {
+    #[inline]
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        alloc::alloc(layout)
+    }
+
+    #[inline]
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        alloc::dealloc(ptr, layout)
+    }
+
+    #[inline]
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        alloc::alloc_zeroed(layout)
+    }
+
+    #[inline]
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        alloc::realloc(ptr, layout, new_size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        cell::Cell,
+        panic::{self, AssertUnwindSafe},
+    };
+
+    use super::*;
+    use crate::{
+        collections::vec::ImposterVec, Imposter, ImposterType, PinnedImposter, PushError,
+        RawMemory, TryReserveError,
+    };
+
+    #[derive(Clone, Copy)]
+    struct Tracking<'a>(&'a Cell<usize>);
+
+    unsafe impl GlobalAlloc for Tracking<'_> {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            self.0.set(self.0.get() + layout.size());
+            Global.alloc(layout)
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            self.0.set(self.0.get() - layout.size());
+            Global.dealloc(ptr, layout)
+        }
+
+        unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+            self.0.set(self.0.get() - layout.size() + new_size);
+            Global.realloc(ptr, layout, new_size)
+        }
+    }
+
+    #[test]
+    fn tracked_imposter() {
+        let bytes = Cell::new(0);
+        let ty = ImposterType::cloneable::<[u64; 4]>();
+        let imposter = Imposter::with_type_in([1u64; 4], ty, Tracking(&bytes)).unwrap();
+        assert_eq!(bytes.get(), 32);
+
+        let clone = imposter.try_clone().unwrap();
+        assert_eq!(bytes.get(), 64);
+        assert_eq!(clone.downcast::<[u64; 4]>().unwrap(), [1; 4]);
+        drop(imposter);
+        assert_eq!(bytes.get(), 0);
+
+        let imposter = Imposter::new_in(42u8, Tracking(&bytes));
+        assert!(imposter.is_inline());
+        assert_eq!(bytes.get(), 0);
+    }
+
+    #[test]
+    fn tracked_vec() {
+        let bytes = Cell::new(0);
+        let mut vec = ImposterVec::new_in::<u64>(Tracking(&bytes));
+        vec.push_item(1u64).unwrap();
+        vec.push_item(2u64).unwrap();
+        vec.push_imposter(Imposter::new(3u64)).unwrap();
+        assert_eq!(bytes.get(), 32);
+
+        let imposter = vec.swap_remove(0).unwrap();
+        assert_eq!(imposter.downcast::<u64>().unwrap(), 1);
+        assert_eq!(vec.as_slice::<u64>().unwrap(), [3, 2]);
+        drop(vec);
+        assert_eq!(bytes.get(), 0);
+    }
+
+    #[test]
+    fn tracked_panic_drop() {
+        struct Bomb {
+            _data: [u64; 4],
+        }
+
+        impl Drop for Bomb {
+            fn drop(&mut self) {
+                panic!("boom");
+            }
+        }
+
+        let bytes = Cell::new(0);
+        let imposter = Imposter::new_in(Bomb { _data: [0; 4] }, Tracking(&bytes));
+        let pinned = PinnedImposter::new_in(Bomb { _data: [0; 4] }, Tracking(&bytes));
+        assert_eq!(bytes.get(), 64);
+
+        let result = panic::catch_unwind(AssertUnwindSafe(move || drop(imposter)));
+        assert!(result.is_err());
+        let result = panic::catch_unwind(AssertUnwindSafe(move || drop(pinned)));
+        assert!(result.is_err());
+        assert_eq!(bytes.get(), 0);
+    }
+
+    /// Fails every allocation
+    #[derive(Clone, Copy)]
+    struct Failing;
+
+    unsafe impl GlobalAlloc for Failing {
+        unsafe fn alloc(&self, _layout: Layout) -> *mut u8 {
+            std::ptr::null_mut()
+        }
+
+        unsafe fn dealloc(&self, _ptr: *mut u8, _layout: Layout) {
+            unreachable!()
+        }
+    }
+
+    #[test]
+    fn failing_alloc() {
+        let mut memory = RawMemory::new_in::<u64>(Failing);
+        let error = memory.try_resize(4).unwrap_err();
+        assert_eq!(
+            error,
+            TryReserveError::AllocError(Layout::new::<[u64; 4]>())
+        );
+        assert_eq!(memory.capacity(), 0);
+
+        let mut vec = ImposterVec::new_in::<u64>(Failing);
+        assert!(vec.try_reserve(1).is_err());
+        let error = vec.try_push_item(42u64).unwrap_err();
+        assert!(matches!(error.error(), PushError::TryReserve(_)));
+        assert_eq!(error.into_value(), 42);
+        let error = vec.try_push_imposter(Imposter::new(42u32)).unwrap_err();
+        assert!(matches!(error.error(), PushError::TypeMismatch(_)));
+        let error = unsafe { vec.try_push_with(|_| Ok::<_, TryReserveError>(())) };
+        assert!(matches!(error, Err(TryReserveError::AllocError(_))));
+        assert!(vec.is_empty());
+
+        let mut vec = ImposterVec::new_in::<()>(Failing);
+        vec.try_push_item(()).unwrap();
+        assert_eq!(vec.len(), 1);
+    }
+
+    #[test]
+    fn over_aligned_zst() {
+        // zero sized, but too aligned to be stored inline
+        #[repr(align(16))]
+        #[derive(Debug, PartialEq)]
+        struct Zst;
+
+        let imposter = Imposter::new_in(Zst, Failing);
+        assert!(!imposter.is_inline());
+        assert_eq!(imposter.data().as_ptr() as usize % 16, 0);
+        let (ptr, ty, alloc) = imposter.into_raw_parts_with_alloc();
+        let imposter = unsafe { Imposter::from_raw_parts_in(ptr, ty, alloc) };
+        let imposter = imposter.map(|zst: Zst| zst).unwrap();
+        assert_eq!(imposter.downcast::<Zst>().unwrap(), Zst);
+
+        let pinned = PinnedImposter::new_in(Zst, Failing);
+        assert!(pinned.downcast_ref::<Zst>().is_some());
+        drop(pinned);
+
+        let mut vec = ImposterVec::new_in::<Zst>(Failing);
+        vec.push_item(Zst).unwrap();
+        vec.push_item(Zst).unwrap();
+        let imposter = vec.swap_remove(0).unwrap();
+        assert_eq!(imposter.downcast::<Zst>().unwrap(), Zst);
+        assert_eq!(vec.len(), 1);
+    }
+
+    /// Forwards to [`Global`], but can not be cloned
+    struct Unique;
+
+    unsafe impl GlobalAlloc for Unique {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            Global.alloc(layout)
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            Global.dealloc(ptr, layout)
+        }
+    }
+
+    #[test]
+    fn unique_alloc_vec() {
+        let mut vec = ImposterVec::new_in::<String>(Unique);
+        vec.push_item(String::from("red")).unwrap();
+        vec.push_item(String::from("sus")).unwrap();
+        assert!(vec.swap_remove_in(2, Global).is_none());
+
+        let imposter = vec.swap_remove_in(0, Global).unwrap();
+        assert_eq!(imposter.downcast::<String>().unwrap(), "red");
+        let imposter = vec.swap_remove_in(0, Unique).unwrap();
+        assert_eq!(imposter.downcast::<String>().unwrap(), "sus");
+        assert!(vec.is_empty());
+    }
+}
@@ -1 +1,3 @@
+pub mod scoped_vec;
+pub mod sync_vec;
 pub mod vec;
@@ -0,0 +1,333 @@
+use std::{
+    fmt,
+    marker::PhantomData,
+    mem::{self, ManuallyDrop},
+};
+
+use crate::{
+    collections::vec::ImposterVec, ImposterError, ImposterType, ImposterTypeId, OutOfBounds,
+    PushError, Rejected, Scoped, ScopedImposter, TryReserveError, TypeMismatch,
+};
+
+/// A type erased vector holding items that live for the lifetime `'a`
+///
+/// Unlike [`ImposterVec`], this may contain items that borrow from their surroundings.
+/// Any items accessed from this vec can never outlive `'a`.
+pub struct ScopedImposterVec<'a> {
+    inner: ManuallyDrop<ImposterVec>,
+    _scope: PhantomData<fn(&'a ()) -> &'a ()>,
+}
+
+impl Drop for ScopedImposterVec<'_> {
+    #[inline]
+    fn drop(&mut self) {
+        // SAFETY:
+        // the inner vec is never used again
+        unsafe { ManuallyDrop::drop(&mut self.inner) }
+    }
+}
+
+impl fmt::Debug for ScopedImposterVec<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ScopedImposterVec")
+            .field("type_name", &self.type_name())
+            .field("len", &self.len())
+            .finish()
+    }
+}
+
+impl<'a> ScopedImposterVec<'a> {
+    /// Creates a new `ScopedImposterVec` that can hold items of type `T`
+    #[inline]
+    pub fn new<T: Scoped<'a>>() -> Self {
+        // SAFETY:
+        // `T` and `T::Static` only differ in lifetimes,
+        // so they share the same layout and drop function
+        unsafe { Self::from_vec_unchecked(ImposterVec::new::<T::Static>()) }
+    }
+
+    /// Creates a new `ScopedImposterVec` with the initial value `imposter`
+    #[inline]
+    pub fn from_imposter(imposter: ScopedImposter<'a>) -> Self {
+        // SAFETY:
+        // the data inside the imposter is valid for `'a`
+        unsafe { Self::from_vec_unchecked(ImposterVec::from_imposter(imposter.into_inner())) }
+    }
+
+    /// Wraps an existing `vec` into a scoped vec
+    ///
+    /// # Safety
+    /// The items inside `vec` must be valid for the lifetime `'a`
+    #[inline]
+    pub unsafe fn from_vec_unchecked(vec: ImposterVec) -> Self {
+        Self {
+            inner: ManuallyDrop::new(vec),
+            _scope: PhantomData,
+        }
+    }
+
+    /// Returns the inner [`ImposterVec`]
+    ///
+    /// # Safety
+    /// The returned vec must not be used to access its items after `'a` has ended
+    #[inline]
+    pub unsafe fn into_inner(mut self) -> ImposterVec {
+        let inner = ManuallyDrop::take(&mut self.inner);
+        mem::forget(self);
+        inner
+    }
+
+    /// Returns the [`ImposterType`] of the items contained in this vec
+    ///
+    /// All lifetimes in the described type are replaced with `'static`
+    #[inline]
+    pub fn imposter_type(&self) -> &'static ImposterType {
+        self.inner.imposter_type()
+    }
+
+    /// Returns the type id of the items in this vec with all lifetimes replaced with `'static`
+    #[inline]
+    pub fn type_id(&self) -> ImposterTypeId {
+        self.inner.type_id()
+    }
+
+    /// Returns the name of the type of the items contained in this vec
+    #[inline]
+    pub fn type_name(&self) -> &'static str {
+        self.inner.type_name()
+    }
+
+    /// Returns true if `T` matches the internal item type
+    #[inline]
+    pub fn is_type<T: Scoped<'a>>(&self) -> bool {
+        self.inner.is_type::<T::Static>()
+    }
+
+    /// Returns a [`TypeMismatch`] between this vecs type and `T`
+    #[inline]
+    fn mismatch<T: Scoped<'a>>(&self) -> TypeMismatch {
+        self.imposter_type().mismatch::<T::Static>()
+    }
+
+    /// Checks that `T` matches this vecs type and that `index` is in bounds
+    #[inline]
+    fn check_access<T: Scoped<'a>>(&self, index: usize) -> Result<(), ImposterError> {
+        if !self.is_type::<T>() {
+            return Err(self.mismatch::<T>().into());
+        }
+
+        if index >= self.len() {
+            return Err(OutOfBounds::new(index, self.len()).into());
+        }
+
+        Ok(())
+    }
+
+    /// Appends a [`ScopedImposter`] to the end of the vector, returning `Ok(())`.
+    ///
+    /// If the imposter is not valid for this vec, it will be returned in `Err`
+    #[inline]
+    pub fn push_imposter(
+        &mut self,
+        imposter: ScopedImposter<'a>,
+    ) -> Result<(), Rejected<ScopedImposter<'a>>> {
+        if imposter.type_id() != self.type_id() {
+            let error = TypeMismatch::new(self.type_name(), imposter.type_name());
+            return Err(Rejected::new(imposter, error));
+        }
+
+        // SAFETY:
+        // the type is checked above, and the data is valid for `'a`
+        unsafe { self.inner.push_imposter_unchecked(imposter.into_inner()) };
+        Ok(())
+    }
+
+    /// Appends `item` to the end of the vector, returning `Ok(())`.
+    ///
+    /// If the item is not valid for this vec, it will be given back in `Err`
+    #[inline]
+    pub fn push_item<T: Scoped<'a>>(&mut self, item: T) -> Result<(), Rejected<T>> {
+        if !self.is_type::<T>() {
+            return Err(Rejected::new(item, self.mismatch::<T>()));
+        }
+
+        // SAFETY:
+        // the type is checked above
+        unsafe { self.inner.push_item_unchecked(item) };
+        Ok(())
+    }
+
+    /// Appends a [`ScopedImposter`] to the end of the vector, returning `Ok(())`.
+    ///
+    /// If the imposter is not valid for this vec, or memory for it could not be reserved,
+    /// it will be returned in `Err`
+    pub fn try_push_imposter(
+        &mut self,
+        imposter: ScopedImposter<'a>,
+    ) -> Result<(), Rejected<ScopedImposter<'a>, PushError>> {
+        if imposter.type_id() != self.type_id() {
+            let error = TypeMismatch::new(self.type_name(), imposter.type_name());
+            return Err(Rejected::new(imposter, error.into()));
+        }
+
+        if let Err(error) = self.inner.try_reserve(1) {
+            return Err(Rejected::new(imposter, error.into()));
+        }
+
+        // SAFETY:
+        // the type is checked above, and the data is valid for `'a`
+        unsafe { self.inner.push_imposter_unchecked(imposter.into_inner()) };
+        Ok(())
+    }
+
+    /// Appends `item` to the end of the vector, returning `Ok(())`.
+    ///
+    /// If the item is not valid for this vec, or memory for it could not be reserved,
+    /// it will be given back in `Err`
+    pub fn try_push_item<T: Scoped<'a>>(&mut self, item: T) -> Result<(), Rejected<T, PushError>> {
+        if !self.is_type::<T>() {
+            return Err(Rejected::new(item, self.mismatch::<T>().into()));
+        }
+
+        if let Err(error) = self.inner.try_reserve(1) {
+            return Err(Rejected::new(item, error.into()));
+        }
+
+        // SAFETY:
+        // the type is checked above
+        unsafe { self.inner.push_item_unchecked(item) };
+        Ok(())
+    }
+
+    /// Reserves capacity for at least `additional` more items
+    #[inline]
+    pub fn reserve(&mut self, additional: usize) {
+        self.inner.reserve(additional)
+    }
+
+    /// Reserves capacity for at least `additional` more items, returning `Ok(())`
+    ///
+    /// If the new capacity overflows or the allocation fails, a [`TryReserveError`] is returned
+    #[inline]
+    pub fn try_reserve(&mut self, additional: usize) -> Result<(), TryReserveError> {
+        self.inner.try_reserve(additional)
+    }
+
+    /// Returns a reference to the item of type `T` stored at `index` as `Ok(&T)`
+    ///
+    /// If `T` does not match this vecs type, or the index is out of bounds, returns an [`ImposterError`]
+    #[inline]
+    pub fn get<T: Scoped<'a>>(&self, index: usize) -> Result<&T, ImposterError> {
+        self.check_access::<T>(index)?;
+        Ok(unsafe { self.inner.get_unchecked(index) })
+    }
+
+    /// Returns a mutable reference to the item of type `T` stored at `index` as `Ok(&mut T)`
+    ///
+    /// If `T` does not match this vecs type, or the index is out of bounds, returns an [`ImposterError`]
+    #[inline]
+    pub fn get_mut<T: Scoped<'a>>(&mut self, index: usize) -> Result<&mut T, ImposterError> {
+        self.check_access::<T>(index)?;
+        Ok(unsafe { self.inner.get_mut_unchecked(index) })
+    }
+
+    /// Returns this vec as a reference to slice of type `T`
+    ///
+    /// Returns a [`TypeMismatch`] if `T` does not match this vecs type
+    #[inline]
+    pub fn as_slice<T: Scoped<'a>>(&self) -> Result<&[T], TypeMismatch> {
+        if !self.is_type::<T>() {
+            return Err(self.mismatch::<T>());
+        }
+
+        Ok(unsafe { self.inner.as_slice_unchecked() })
+    }
+
+    /// Returns this vec as a mutable reference to slice of type `T`
+    ///
+    /// Returns a [`TypeMismatch`] if `T` does not match this vecs type
+    #[inline]
+    pub fn as_slice_mut<T: Scoped<'a>>(&mut self) -> Result<&mut [T], TypeMismatch> {
+        if !self.is_type::<T>() {
+            return Err(self.mismatch::<T>());
+        }
+
+        Ok(unsafe { self.inner.as_slice_mut_unchecked() })
+    }
+
+    /// Removes and returns the [`ScopedImposter`] at `index`, swapping it with the last item in the vec
+    ///
+    /// Returns `None` if `index` is out of bounds
+    #[inline]
+    pub fn swap_remove(&mut self, index: usize) -> Option<ScopedImposter<'a>> {
+        let imposter = self.inner.swap_remove(index)?;
+        // SAFETY:
+        // all the items in this vec are valid for `'a`
+        Some(unsafe { ScopedImposter::from_imposter_unchecked(imposter) })
+    }
+
+    /// Drops the value at `index` by swapping it with the last value, returning `true`
+    ///
+    /// Returns `false` if the index is out of bounds, and does not drop anything
+    #[inline]
+    pub fn swap_drop(&mut self, index: usize) -> bool {
+        self.inner.swap_drop(index)
+    }
+
+    /// Shortens the vector to `len` items, calling the drop function of the removed items if necessary
+    #[inline]
+    pub fn truncate(&mut self, len: usize) {
+        self.inner.truncate(len)
+    }
+
+    /// Clears all the elements in the vector, calling their drop function if necessary
+    #[inline]
+    pub fn clear(&mut self) {
+        self.inner.clear()
+    }
+
+    /// Returns the number of items in the vec
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    /// Returns `true` if the vec is empty
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scoped_vec() {
+        let words = [String::from("red"), String::from("sus")];
+        let mut vec = ScopedImposterVec::new::<&str>();
+        vec.push_item(words[0].as_str()).unwrap();
+        vec.push_imposter(ScopedImposter::new(words[1].as_str()))
+            .unwrap();
+        assert!(vec.push_item(42u32).is_err());
+        assert_eq!(vec.as_slice::<&str>().unwrap(), ["red", "sus"]);
+        assert!(matches!(
+            vec.get::<&str>(2),
+            Err(ImposterError::OutOfBounds(_))
+        ));
+
+        let imposter = vec.swap_remove(0).unwrap();
+        assert_eq!(imposter.downcast::<&str>().unwrap(), "red");
+        assert_eq!(vec.get::<&str>(0).unwrap(), &"sus");
+
+        vec.try_reserve(2).unwrap();
+        vec.try_push_item(words[1].as_str()).unwrap();
+        vec.try_push_imposter(ScopedImposter::new(words[0].as_str()))
+            .unwrap();
+        let error = vec.try_push_item(42u32).unwrap_err();
+        assert!(matches!(error.error(), PushError::TypeMismatch(_)));
+        assert!(vec.try_push_imposter(ScopedImposter::new(42u32)).is_err());
+        assert_eq!(vec.as_slice::<&str>().unwrap(), ["sus", "sus", "red"]);
+    }
+}
@@ -0,0 +1,291 @@
+use std::{fmt, ops::Deref};
+
+use crate::{
+    collections::vec::ImposterVec, ImposterError, PushError, Rejected, SendImposter, SyncImposter,
+    TryReserveError, TypeMismatch,
+};
+
+macro_rules! thread_safe_vec {
+    ($(#[$meta:meta])* $name:ident<$imposter:ident>: $($bound:tt)+) => {
+        $(#[$meta])*
+        pub struct $name(ImposterVec);
+
+        impl fmt::Debug for $name {
+            #[inline]
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                self.0.fmt(f)
+            }
+        }
+
+        impl Deref for $name {
+            type Target = ImposterVec;
+
+            #[inline]
+            fn deref(&self) -> &Self::Target {
+                &self.0
+            }
+        }
+
+        impl From<$name> for ImposterVec {
+            #[inline]
+            fn from(vec: $name) -> Self {
+                vec.0
+            }
+        }
+
+        impl $name {
+            /// Creates a new vec that can hold items of type `T`
+            #[inline]
+            pub fn new<T: 'static + $($bound)+>() -> Self {
+                Self(ImposterVec::new::<T>())
+            }
+
+            /// Creates a new vec that can hold items of type `T` and can be cloned using [`ImposterVec::try_clone`]
+            #[inline]
+            pub fn new_cloneable<T: Clone + 'static + $($bound)+>() -> Self {
+                Self(ImposterVec::new_cloneable::<T>())
+            }
+
+            /// Creates a new vec that can hold items of type `T` and formats its items when debugged
+            #[inline]
+            pub fn new_debuggable<T: fmt::Debug + 'static + $($bound)+>() -> Self {
+                Self(ImposterVec::new_debuggable::<T>())
+            }
+
+            /// Creates a new vec with the initial value `imposter`
+            #[inline]
+            pub fn from_imposter(imposter: $imposter) -> Self {
+                Self(ImposterVec::from_imposter(imposter.into()))
+            }
+
+            /// Wraps an existing `vec`
+            ///
+            /// # Safety
+            #[doc = concat!("The type of the items in `vec` must be `", stringify!($($bound)+), "`")]
+            #[inline]
+            pub unsafe fn from_vec_unchecked(vec: ImposterVec) -> Self {
+                Self(vec)
+            }
+
+            /// Returns the inner [`ImposterVec`]
+            #[inline]
+            pub fn into_inner(self) -> ImposterVec {
+                self.0
+            }
+
+            /// Appends an imposter to the end of the vector, returning `Ok(())`.
+            ///
+            /// If the imposter is not valid for this vec, it will be returned in `Err`
+            #[inline]
+            pub fn push_imposter(&mut self, imposter: $imposter) -> Result<(), Rejected<$imposter>> {
+                self.0.push_imposter(imposter.into()).map_err(|e| {
+                    let (imposter, error) = e.into_parts();
+                    // SAFETY: the imposter was unwrapped from a valid wrapper above
+                    let imposter = unsafe { $imposter::from_imposter_unchecked(imposter) };
+                    Rejected::new(imposter, error)
+                })
+            }
+
+            /// Appends an imposter to the end of the vector, returning `Ok(())`.
+            ///
+            /// If the imposter is not valid for this vec, or memory for it could not be reserved,
+            /// it will be returned in `Err`
+            #[inline]
+            pub fn try_push_imposter(
+                &mut self,
+                imposter: $imposter,
+            ) -> Result<(), Rejected<$imposter, PushError>> {
+                self.0.try_push_imposter(imposter.into()).map_err(|e| {
+                    let (imposter, error) = e.into_parts();
+                    // SAFETY: the imposter was unwrapped from a valid wrapper above
+                    let imposter = unsafe { $imposter::from_imposter_unchecked(imposter) };
+                    Rejected::new(imposter, error)
+                })
+            }
+
+            /// Appends `item` to the end of the vector, returning `Ok(())`.
+            ///
+            /// If the item is not valid for this vec, it will be given back in `Err`
+            #[inline]
+            pub fn push_item<T: 'static>(&mut self, item: T) -> Result<(), Rejected<T>> {
+                self.0.push_item(item)
+            }
+
+            /// Appends `item` to the end of the vector, returning `Ok(())`.
+            ///
+            /// If the item is not valid for this vec, or memory for it could not be reserved,
+            /// it will be given back in `Err`
+            #[inline]
+            pub fn try_push_item<T: 'static>(&mut self, item: T) -> Result<(), Rejected<T, PushError>> {
+                self.0.try_push_item(item)
+            }
+
+            /// Reserves capacity for at least `additional` more items
+            #[inline]
+            pub fn reserve(&mut self, additional: usize) {
+                self.0.reserve(additional)
+            }
+
+            /// Reserves capacity for at least `additional` more items, returning `Ok(())`
+            ///
+            /// If the new capacity overflows or the allocation fails, a [`TryReserveError`] is returned
+            #[inline]
+            pub fn try_reserve(&mut self, additional: usize) -> Result<(), TryReserveError> {
+                self.0.try_reserve(additional)
+            }
+
+            /// Returns a mutable reference to the item of type `T` stored at `index` as `Ok(&mut T)`
+            ///
+            /// If `T` does not match this vecs type, or the index is out of bounds, returns an [`ImposterError`]
+            #[inline]
+            pub fn get_mut<T: 'static>(&mut self, index: usize) -> Result<&mut T, ImposterError> {
+                self.0.get_mut(index)
+            }
+
+            /// Returns this vec as a mutable reference to slice of type `T`
+            ///
+            /// Returns a [`TypeMismatch`] if `T` does not match this vecs type
+            #[inline]
+            pub fn as_slice_mut<T: 'static>(&mut self) -> Result<&mut [T], TypeMismatch> {
+                self.0.as_slice_mut()
+            }
+
+            /// Removes and returns the imposter at `index`, swapping it with the last item in the vec
+            ///
+            /// Returns `None` if `index` is out of bounds
+            #[inline]
+            pub fn swap_remove(&mut self, index: usize) -> Option<$imposter> {
+                let imposter = self.0.swap_remove(index)?;
+                // SAFETY: all the items in this vec are valid for the wrapper
+                Some(unsafe { $imposter::from_imposter_unchecked(imposter) })
+            }
+
+            /// Drops the value at `index` by swapping it with the last value, returning `true`
+            ///
+            /// Returns `false` if the index is out of bounds, and does not drop anything
+            #[inline]
+            pub fn swap_drop(&mut self, index: usize) -> bool {
+                self.0.swap_drop(index)
+            }
+
+            /// Appends the default value of the item type to the end of the vector, returning `true`
+            ///
+            /// Returns `false` if no default constructor was recorded for the item type.
+            #[inline]
+            pub fn push_default(&mut self) -> bool {
+                self.0.push_default()
+            }
+
+            /// Resizes the vector to `len` items using the default constructor of the item type, returning `true`
+            ///
+            /// Returns `false` if the vec has to grow but no default constructor was recorded for the item type.
+            #[inline]
+            pub fn resize_default(&mut self, len: usize) -> bool {
+                self.0.resize_default(len)
+            }
+
+            /// Shortens the vector to `len` items, calling the drop function of the removed items if necessary
+            #[inline]
+            pub fn truncate(&mut self, len: usize) {
+                self.0.truncate(len)
+            }
+
+            /// Clears all the elements in the vector, calling their drop function if necessary
+            #[inline]
+            pub fn clear(&mut self) {
+                self.0.clear()
+            }
+
+            /// Returns a clone of this vec and all of its items
+            ///
+            /// Returns `None` if no clone function was recorded for the item type.
+            #[inline]
+            pub fn try_clone(&self) -> Option<Self> {
+                self.0.try_clone().map(Self)
+            }
+
+            /// Converts this vec into a typed [`Vec`]
+            ///
+            /// Returns this vec in `Err` if `T` does not match this vec's type
+            #[inline]
+            pub fn into_vec<T: 'static>(self) -> Result<Vec<T>, Rejected<Self>> {
+                self.0.into_vec().map_err(|e| {
+                    let (vec, error) = e.into_parts();
+                    Rejected::new(Self(vec), error)
+                })
+            }
+        }
+    };
+}
+
+thread_safe_vec! {
+    /// An [`ImposterVec`] that may only contain types that are [`Send`]
+    ///
+    /// Because every item it can hold is `Send`, this vec can safely be sent to other threads.
+    SendImposterVec<SendImposter>: Send
+}
+
+thread_safe_vec! {
+    /// An [`ImposterVec`] that may only contain types that are [`Send`] and [`Sync`]
+    ///
+    /// Because every item it can hold is `Send` and `Sync`,
+    /// this vec can safely be sent to and shared between other threads.
+    SyncImposterVec<SyncImposter>: Send + Sync
+}
+
+// SAFETY:
+// a `SendImposterVec` can only be created for items that are `Send`
+unsafe impl Send for SendImposterVec {}
+
+// SAFETY:
+// a `SyncImposterVec` can only be created for items that are `Send + Sync`
+unsafe impl Send for SyncImposterVec {}
+unsafe impl Sync for SyncImposterVec {}
+
+impl From<SyncImposterVec> for SendImposterVec {
+    #[inline]
+    fn from(vec: SyncImposterVec) -> Self {
+        Self(vec.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::Arc, thread};
+
+    use super::*;
+
+    #[test]
+    fn send_vec() {
+        let mut vec = SendImposterVec::new::<String>();
+        vec.push_item(String::from("red")).unwrap();
+        let mut vec = thread::spawn(move || {
+            let mut vec = vec;
+            vec.push_imposter(SendImposter::new(String::from("sus")))
+                .unwrap();
+            vec
+        })
+        .join()
+        .unwrap();
+        assert!(vec.push_imposter(SendImposter::new(42u32)).is_err());
+        vec.try_push_imposter(SendImposter::new(String::from("crew")))
+            .unwrap();
+        let error = vec.try_push_imposter(SendImposter::new(42u32)).unwrap_err();
+        assert!(matches!(error.error(), PushError::TypeMismatch(_)));
+        assert_eq!(vec.as_slice::<String>().unwrap(), ["red", "sus", "crew"]);
+    }
+
+    #[test]
+    fn sync_vec() {
+        let vec = Arc::new({
+            let mut vec = SyncImposterVec::new::<u32>();
+            vec.push_item(42u32).unwrap();
+            vec
+        });
+        let shared = vec.clone();
+        let value = thread::spawn(move || *shared.get::<u32>(0).unwrap())
+            .join()
+            .unwrap();
+        assert_eq!(value, 42);
+    }
+}
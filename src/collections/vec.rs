@@ -1,17 +1,40 @@
-use std::{any::TypeId, mem, ptr, slice};
+use std::{
+    alloc::{handle_alloc_error, GlobalAlloc},
+    fmt,
+    marker::PhantomData,
+    mem, ptr, slice,
+};
 
-use crate::{Imposter, ImposterDrop, RawMemory};
+use crate::{
+    DynCaster, ErasedDebug, Global, Imposter, ImposterError, ImposterMut, ImposterRef,
+    ImposterType, ImposterTypeId, OutOfBounds, PushError, RawMemory, Rejected, TryReserveError,
+    TypeMismatch, TypeRegistry,
+};
 
 /// A type erased vector
-#[derive(Debug)]
-pub struct ImposterVec {
-    typeid: TypeId,
-    memory: RawMemory,
+///
+/// All items are stored using the allocator `A`, which defaults to the [`Global`] allocator.
+pub struct ImposterVec<A: GlobalAlloc = Global> {
+    memory: RawMemory<A>,
     len: usize,
-    drop: Option<ImposterDrop>,
 }
 
-impl Drop for ImposterVec {
+impl<A: GlobalAlloc> fmt::Debug for ImposterVec<A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.imposter_type().debug_fn().is_some() {
+            let items = (0..self.len).map(|index| unsafe { self.get_debug_unchecked(index) });
+            return f.debug_list().entries(items).finish();
+        }
+
+        f.debug_struct("ImposterVec")
+            .field("type_name", &self.type_name())
+            .field("len", &self.len)
+            .field("capacity", &self.memory.capacity())
+            .finish()
+    }
+}
+
+impl<A: GlobalAlloc> Drop for ImposterVec<A> {
     #[inline]
     fn drop(&mut self) {
         self.clear()
@@ -22,45 +45,184 @@ impl ImposterVec {
     /// Creates a new `ImposterVec` that can hold items of type `T`
     #[inline]
     pub fn new<T: 'static>() -> Self {
+        Self::with_type(ImposterType::of::<T>())
+    }
+
+    /// Creates a new `ImposterVec` that can hold items of type `T` and can be cloned using [`ImposterVec::try_clone`]
+    #[inline]
+    pub fn new_cloneable<T: Clone + 'static>() -> Self {
+        Self::with_type(ImposterType::cloneable::<T>())
+    }
+
+    /// Creates a new `ImposterVec` that can hold items of type `T` and formats its items when debugged
+    #[inline]
+    pub fn new_debuggable<T: fmt::Debug + 'static>() -> Self {
+        Self::with_type(ImposterType::debuggable::<T>())
+    }
+
+    /// Creates a new `ImposterVec` that can hold items described by `ty`
+    #[inline]
+    pub fn with_type(ty: &'static ImposterType) -> Self {
+        Self::with_type_in(ty, Global)
+    }
+
+    /// Creates a new `ImposterVec` that can hold items of the type registered in `registry` as `name`
+    ///
+    /// If `name` is not registered, `None` is returned.
+    #[inline]
+    pub fn from_registered(registry: &TypeRegistry, name: &str) -> Option<Self> {
+        Self::from_registered_in(registry, name, Global)
+    }
+
+    /// Creates a new `ImposterVec` with the initial value `imposter`
+    #[inline]
+    pub fn from_imposter(imposter: Imposter) -> Self {
+        Self::from_imposter_in(imposter, Global)
+    }
+
+    /// Converts this `ImposterVec` into a typed [`Vec`]
+    ///
+    /// Returns this vec in `Err` if `T` does not match this vec's type
+    #[inline]
+    pub fn into_vec<T: 'static>(self) -> Result<Vec<T>, Rejected<Self>> {
+        if !self.is_type::<T>() {
+            let error = self.mismatch::<T>();
+            return Err(Rejected::new(self, error));
+        }
+
+        Ok(unsafe { self.into_vec_unchecked() })
+    }
+
+    /// Converts this `ImposterVec` into a typed [`Vec`]
+    ///
+    /// # Safety
+    /// type `T` must match this vecs type
+    #[inline]
+    pub unsafe fn into_vec_unchecked<T>(self) -> Vec<T> {
+        let vec = Vec::from_raw_parts(
+            self.memory.ptr() as *mut T,
+            self.len,
+            self.memory.capacity(),
+        );
+        mem::forget(self);
+        vec
+    }
+}
+
+impl<A: GlobalAlloc + Clone> ImposterVec<A> {
+    /// Removes and returns the [`Imposter`] at `index`, swapping it with the last item in the vec
+    ///
+    /// The imposter is allocated using a clone of this vec's allocator, see [`ImposterVec::swap_remove_in`].
+    /// Returns `None` if `index` is out of bounds
+    #[inline]
+    pub fn swap_remove(&mut self, index: usize) -> Option<Imposter<A>> {
+        let alloc = self.allocator().clone();
+        self.swap_remove_in(index, alloc)
+    }
+
+    /// Removes and returns the [`Imposter`] at `index`, swapping it with the last item in the vec
+    ///
+    /// # Safety
+    /// `index` must be valid for this vec
+    #[inline]
+    pub unsafe fn swap_remove_unchecked(&mut self, index: usize) -> Imposter<A> {
+        let alloc = self.allocator().clone();
+        self.swap_remove_unchecked_in(index, alloc)
+    }
+
+    /// Returns a clone of this vec and all of its items
+    ///
+    /// Returns `None` if no clone function was recorded for the item type.
+    /// See [`ImposterVec::new_cloneable`].
+    pub fn try_clone(&self) -> Option<Self> {
+        let clone = self.imposter_type().clone_fn()?;
+        let mut vec = Self::with_type_in(self.imposter_type(), self.allocator().clone());
+        vec.memory.resize(self.len);
+        for index in 0..self.len {
+            unsafe {
+                let src = self.memory.index_ptr_unchecked(index);
+                (clone)(src, vec.memory.index_ptr_unchecked(index));
+            }
+
+            // increment as we go so that a panicking clone only drops initialized items
+            vec.len += 1;
+        }
+
+        Some(vec)
+    }
+}
+
+impl<A: GlobalAlloc> ImposterVec<A> {
+    /// Creates a new `ImposterVec` that can hold items of type `T`, allocated using `alloc`
+    #[inline]
+    pub fn new_in<T: 'static>(alloc: A) -> Self {
+        Self::with_type_in(ImposterType::of::<T>(), alloc)
+    }
+
+    /// Creates a new `ImposterVec` that can hold items described by `ty`, allocated using `alloc`
+    #[inline]
+    pub fn with_type_in(ty: &'static ImposterType, alloc: A) -> Self {
         Self {
-            typeid: TypeId::of::<T>(),
-            memory: RawMemory::new::<T>(),
+            memory: RawMemory::from_type_in(ty, alloc),
             len: 0,
-            drop: match mem::needs_drop::<T>() {
-                false => None,
-                true => Some(Imposter::drop_impl::<T>),
-            },
         }
     }
 
-    /// Creates a new `ImposterVec` with the initial value `imposter`
+    /// Creates a new `ImposterVec` that can hold items of the type registered in `registry` as `name`,
+    /// allocated using `alloc`
+    ///
+    /// If `name` is not registered, `None` is returned.
     #[inline]
-    pub fn from_imposter(imposter: Imposter) -> Self {
-        let mut memory = RawMemory::with_element_layout(imposter.layout());
+    pub fn from_registered_in(registry: &TypeRegistry, name: &str, alloc: A) -> Option<Self> {
+        Some(Self::with_type_in(registry.type_of(name)?, alloc))
+    }
+
+    /// Creates a new `ImposterVec` with the initial value `imposter`, allocated using `alloc`
+    #[inline]
+    pub fn from_imposter_in<B: GlobalAlloc>(imposter: Imposter<B>, alloc: A) -> Self {
+        let mut memory = RawMemory::from_type_in(imposter.imposter_type(), alloc);
         memory.resize(1);
         unsafe { memory.copy_to_index_unchecked(imposter.data().as_ptr(), 0) };
+        imposter.dispose_and_forget();
 
-        Self {
-            typeid: imposter.type_id(),
-            memory,
-            len: 1,
-            drop: imposter.drop_fn(),
-        }
+        Self { memory, len: 1 }
     }
 
-    /// Returns the [`TypeId`] of the items contained in this vec
+    /// Returns a reference to the allocator used by this vec
     #[inline]
-    pub fn type_id(&self) -> TypeId {
-        self.typeid
+    pub fn allocator(&self) -> &A {
+        self.memory.allocator()
+    }
+
+    /// Returns the [`ImposterType`] of the items contained in this vec
+    #[inline]
+    pub fn imposter_type(&self) -> &'static ImposterType {
+        self.memory.imposter_type()
+    }
+
+    /// Returns the [`ImposterTypeId`] of the items contained in this vec
+    #[inline]
+    pub fn type_id(&self) -> ImposterTypeId {
+        self.imposter_type().type_id()
+    }
+
+    /// Returns the name of the type of the items contained in this vec
+    #[inline]
+    pub fn type_name(&self) -> &'static str {
+        self.imposter_type().type_name()
     }
 
     /// Appends an [`Imposter`] to the end of the vector, returning `Ok(())`.
     ///
-    /// If the imposter is not valid for this vec, it will be returned as `Err(Imposter)`
+    /// If the imposter is not valid for this vec, it will be returned in `Err`
     #[inline]
-    pub fn push_imposter(&mut self, imposter: Imposter) -> Result<(), Imposter> {
-        if imposter.type_id() != self.typeid {
-            return Err(imposter);
+    pub fn push_imposter<B: GlobalAlloc>(
+        &mut self,
+        imposter: Imposter<B>,
+    ) -> Result<(), Rejected<Imposter<B>>> {
+        if imposter.type_id() != self.type_id() {
+            let error = TypeMismatch::new(self.type_name(), imposter.type_name());
+            return Err(Rejected::new(imposter, error));
         }
 
         unsafe { self.push_imposter_unchecked(imposter) };
@@ -72,18 +234,18 @@ impl ImposterVec {
     /// # Safety
     /// the `imposter` type must match the type of this vec
     #[inline]
-    pub unsafe fn push_imposter_unchecked(&mut self, imposter: Imposter) {
+    pub unsafe fn push_imposter_unchecked<B: GlobalAlloc>(&mut self, imposter: Imposter<B>) {
         self.push_raw_unchecked(imposter.data().as_ptr());
         imposter.dispose_and_forget();
     }
 
     /// Appends `item` to the end of the vector, returning `Ok(())`.
     ///
-    /// If the item is not valid for this vec, it will be given back as `Some(T)`
+    /// If the item is not valid for this vec, it will be given back in `Err`
     #[inline]
-    pub fn push_item<T: 'static>(&mut self, item: T) -> Result<(), T> {
-        if self.is_type::<T>() {
-            return Err(item);
+    pub fn push_item<T: 'static>(&mut self, item: T) -> Result<(), Rejected<T>> {
+        if !self.is_type::<T>() {
+            return Err(Rejected::new(item, self.mismatch::<T>()));
         }
 
         unsafe { self.push_item_unchecked(item) };
@@ -95,7 +257,7 @@ impl ImposterVec {
     /// # Safety
     /// type `T` must match this vecs type
     #[inline]
-    pub unsafe fn push_item_unchecked<T: 'static>(&mut self, item: T) {
+    pub unsafe fn push_item_unchecked<T>(&mut self, item: T) {
         let item_ptr = ptr::NonNull::from(&item).cast::<u8>().as_ptr();
         self.push_raw_unchecked(item_ptr);
         mem::forget(item);
@@ -107,26 +269,138 @@ impl ImposterVec {
     /// `item_ptr` must point to a type that matches this vec
     #[inline]
     pub unsafe fn push_raw_unchecked(&mut self, item_ptr: *mut u8) {
-        let original_length = self.len;
-        if original_length == self.memory.capacity() {
-            let new_length = (self.memory.capacity() * 2).max(1);
-            self.memory.resize(new_length);
+        self.reserve(1);
+        self.memory.copy_to_index_unchecked(item_ptr, self.len);
+        self.len += 1;
+    }
+
+    /// Appends an [`Imposter`] to the end of the vector, returning `Ok(())`.
+    ///
+    /// If the imposter is not valid for this vec, or memory for it could not be reserved,
+    /// it will be returned in `Err`
+    #[inline]
+    pub fn try_push_imposter<B: GlobalAlloc>(
+        &mut self,
+        imposter: Imposter<B>,
+    ) -> Result<(), Rejected<Imposter<B>, PushError>> {
+        if imposter.type_id() != self.type_id() {
+            let error = TypeMismatch::new(self.type_name(), imposter.type_name());
+            return Err(Rejected::new(imposter, error.into()));
         }
 
-        self.memory.copy_to_index_unchecked(item_ptr, self.len);
+        if let Err(error) = self.try_reserve(1) {
+            return Err(Rejected::new(imposter, error.into()));
+        }
+
+        unsafe { self.push_imposter_unchecked(imposter) };
+        Ok(())
+    }
+
+    /// Appends `item` to the end of the vector, returning `Ok(())`.
+    ///
+    /// If the item is not valid for this vec, or memory for it could not be reserved,
+    /// it will be given back in `Err`
+    #[inline]
+    pub fn try_push_item<T: 'static>(&mut self, item: T) -> Result<(), Rejected<T, PushError>> {
+        if !self.is_type::<T>() {
+            return Err(Rejected::new(item, self.mismatch::<T>().into()));
+        }
+
+        if let Err(error) = self.try_reserve(1) {
+            return Err(Rejected::new(item, error.into()));
+        }
+
+        unsafe { self.push_item_unchecked(item) };
+        Ok(())
+    }
+
+    /// Appends `item_ptr` to the end of the vector, returning `Ok(())`
+    ///
+    /// If memory for the item could not be reserved, a [`TryReserveError`] is returned
+    /// and the item at `item_ptr` is left untouched.
+    ///
+    /// # Safety
+    /// `item_ptr` must point to a type that matches this vec
+    #[inline]
+    pub unsafe fn try_push_raw_unchecked(
+        &mut self,
+        item_ptr: *mut u8,
+    ) -> Result<(), TryReserveError> {
+        self.try_reserve(1)?;
+        self.push_raw_unchecked(item_ptr);
+        Ok(())
+    }
+
+    /// Appends an item to the end of the vector that is initialized in place by the fallible `init`
+    ///
+    /// If the memory can not be reserved or `init` fails, the vector is left unchanged.
+    ///
+    /// # Safety
+    /// If `init` returns `Ok`, it must have initialized the memory with a valid item of this vec's type
+    pub(crate) unsafe fn try_push_with<E: From<TryReserveError>>(
+        &mut self,
+        init: impl FnOnce(*mut u8) -> Result<(), E>,
+    ) -> Result<(), E> {
+        self.try_reserve(1)?;
+        init(self.memory.index_ptr_unchecked(self.len))?;
         self.len += 1;
+        Ok(())
     }
 
-    /// Returns a reference to the item of type `T` stored at `index` as `Some(&T)`
+    /// Reserves capacity for at least `additional` more items
+    ///
+    /// The capacity grows at least by doubling, so that repeated pushes stay cheap.
     ///
-    /// If `T` does not match this vecs type, ot the index is out of bounds, returns `None`
+    /// # Panics
+    /// Panics if the new capacity overflows, and aborts if the allocation fails.
+    /// See [`ImposterVec::try_reserve`] for a fallible version.
+    pub fn reserve(&mut self, additional: usize) {
+        match self.try_reserve(additional) {
+            Ok(()) => (),
+            Err(TryReserveError::CapacityOverflow) => panic!("memory overflow"),
+            Err(TryReserveError::AllocError(layout)) => handle_alloc_error(layout),
+        }
+    }
+
+    /// Reserves capacity for at least `additional` more items, returning `Ok(())`
+    ///
+    /// If the new capacity overflows or the allocation fails, a [`TryReserveError`]
+    /// is returned and the vec is left unchanged.
+    pub fn try_reserve(&mut self, additional: usize) -> Result<(), TryReserveError> {
+        let required = self
+            .len
+            .checked_add(additional)
+            .ok_or(TryReserveError::CapacityOverflow)?;
+        if required <= self.memory.capacity() || self.memory.element_layout().size() == 0 {
+            return Ok(());
+        }
+
+        let doubled = self.memory.capacity().saturating_mul(2);
+        match self.memory.try_resize(required.max(doubled)) {
+            // the doubled capacity may be too large even if the required capacity is not
+            Err(_) if doubled > required => self.memory.try_resize(required),
+            result => result,
+        }
+    }
+
+    /// Returns the number of items the vec can hold without allocating
+    ///
+    /// Items of a zero sized type never need to be allocated, so the capacity is [`usize::MAX`].
     #[inline]
-    pub fn get<T: 'static>(&self, index: usize) -> Option<&T> {
-        if index >= self.len || self.is_type::<T>() {
-            return None;
+    pub fn capacity(&self) -> usize {
+        match self.memory.element_layout().size() {
+            0 => usize::MAX,
+            _ => self.memory.capacity(),
         }
+    }
 
-        Some(unsafe { self.get_unchecked(index) })
+    /// Returns a reference to the item of type `T` stored at `index` as `Ok(&T)`
+    ///
+    /// If `T` does not match this vecs type, or the index is out of bounds, returns an [`ImposterError`]
+    #[inline]
+    pub fn get<T: 'static>(&self, index: usize) -> Result<&T, ImposterError> {
+        self.check_access::<T>(index)?;
+        Ok(unsafe { self.get_unchecked(index) })
     }
 
     /// Returns a reference to the item of type `T` stored at `index`
@@ -135,20 +409,17 @@ impl ImposterVec {
     /// - `T` must match this vecs type
     /// - `index` must be valid
     #[inline]
-    pub unsafe fn get_unchecked<T: 'static>(&self, index: usize) -> &T {
+    pub unsafe fn get_unchecked<T>(&self, index: usize) -> &T {
         &*(self.memory.index_ptr_unchecked(index) as *mut T)
     }
 
-    /// Returns a mutable reference to the item of type `T` stored at `index` as `Some(&T)`
+    /// Returns a mutable reference to the item of type `T` stored at `index` as `Ok(&mut T)`
     ///
-    /// If `T` does not match this vecs type, ot the index is out of bounds, returns `None`
+    /// If `T` does not match this vecs type, or the index is out of bounds, returns an [`ImposterError`]
     #[inline]
-    pub fn get_mut<T: 'static>(&mut self, index: usize) -> Option<&mut T> {
-        if index >= self.len || self.is_type::<T>() {
-            return None;
-        }
-
-        Some(unsafe { self.get_mut_unchecked(index) })
+    pub fn get_mut<T: 'static>(&mut self, index: usize) -> Result<&mut T, ImposterError> {
+        self.check_access::<T>(index)?;
+        Ok(unsafe { self.get_mut_unchecked(index) })
     }
 
     /// Returns a mutable reference to the item of type `T` stored at `index`
@@ -157,7 +428,7 @@ impl ImposterVec {
     /// - `T` must match this vecs type
     /// - `index` must be valid
     #[inline]
-    pub unsafe fn get_mut_unchecked<T: 'static>(&mut self, index: usize) -> &mut T {
+    pub unsafe fn get_mut_unchecked<T>(&mut self, index: usize) -> &mut T {
         &mut *(self.memory.index_ptr_unchecked(index) as *mut T)
     }
 
@@ -182,33 +453,123 @@ impl ImposterVec {
         self.memory.index_ptr_unchecked(index)
     }
 
-    /// Removes and returns the [`Imposter`] at `index`, swapping it with the last item in the vec
+    /// Returns a borrowed [`ImposterRef`] to the item at `index` as `Some(ImposterRef)`
+    ///
+    /// Returns `None` if the index is out of bounds
+    #[inline]
+    pub fn get_ref(&self, index: usize) -> Option<ImposterRef<'_>> {
+        if index >= self.len {
+            return None;
+        }
+
+        Some(unsafe { self.get_ref_unchecked(index) })
+    }
+
+    /// Returns a borrowed [`ImposterRef`] to the item at `index`
+    ///
+    /// # Safety
+    /// `index` must be in bounds for this vec
+    #[inline]
+    pub unsafe fn get_ref_unchecked(&self, index: usize) -> ImposterRef<'_> {
+        let ptr = ptr::NonNull::new_unchecked(self.get_ptr_unchecked(index));
+        ImposterRef::from_raw(ptr, self.imposter_type())
+    }
+
+    /// Returns a mutably borrowed [`ImposterMut`] to the item at `index` as `Some(ImposterMut)`
+    ///
+    /// Returns `None` if the index is out of bounds
+    #[inline]
+    pub fn get_mut_ref(&mut self, index: usize) -> Option<ImposterMut<'_>> {
+        if index >= self.len {
+            return None;
+        }
+
+        Some(unsafe { self.get_mut_ref_unchecked(index) })
+    }
+
+    /// Returns a mutably borrowed [`ImposterMut`] to the item at `index`
+    ///
+    /// # Safety
+    /// `index` must be in bounds for this vec
+    #[inline]
+    pub unsafe fn get_mut_ref_unchecked(&mut self, index: usize) -> ImposterMut<'_> {
+        let ptr = ptr::NonNull::new_unchecked(self.get_ptr_unchecked(index));
+        ImposterMut::from_raw(ptr, self.imposter_type())
+    }
+
+    /// Returns an [`ErasedDebug`] that formats the item at `index` as `Some(ErasedDebug)`
+    ///
+    /// Returns `None` if the index is out of bounds
+    #[inline]
+    pub fn get_debug(&self, index: usize) -> Option<ErasedDebug<'_>> {
+        if index >= self.len {
+            return None;
+        }
+
+        Some(unsafe { self.get_debug_unchecked(index) })
+    }
+
+    /// Returns an [`ErasedDebug`] that formats the item at `index`
+    ///
+    /// # Safety
+    /// `index` must be in bounds for this vec
+    #[inline]
+    pub unsafe fn get_debug_unchecked(&self, index: usize) -> ErasedDebug<'_> {
+        ErasedDebug::new(self.get_ptr_unchecked(index), self.imposter_type())
+    }
+
+    /// Returns the item at `index` viewed as the trait object `&Dyn` as `Some(&Dyn)`
+    ///
+    /// Returns `None` if the index is out of bounds, or no [`DynCaster`] for `Dyn` was registered with the item type.
+    /// See [`ImposterTypeBuilder::with_dyn`](crate::ImposterTypeBuilder::with_dyn).
+    #[inline]
+    pub fn get_dyn<Dyn: ?Sized + 'static>(&self, index: usize) -> Option<&Dyn> {
+        let caster = self.imposter_type().dyn_caster::<Dyn>()?;
+        let ptr = self.get_ptr(index)?;
+        Some(unsafe { caster.cast_ref(ptr) })
+    }
+
+    /// Returns the item at `index` viewed as the trait object `&mut Dyn` as `Some(&mut Dyn)`
+    ///
+    /// Returns `None` if the index is out of bounds, or no [`DynCaster`] for `Dyn` was registered with the item type.
+    #[inline]
+    pub fn get_dyn_mut<Dyn: ?Sized + 'static>(&mut self, index: usize) -> Option<&mut Dyn> {
+        let caster = self.imposter_type().dyn_caster::<Dyn>()?;
+        let ptr = self.get_ptr(index)?;
+        Some(unsafe { caster.cast_mut(ptr) })
+    }
+
+    /// Removes and returns the [`Imposter`] at `index` allocated using `alloc`, swapping it with the last item in the vec
     ///
     /// Returns `None` if `index` is out of bounds
     #[inline]
-    pub fn swap_remove(&mut self, index: usize) -> Option<Imposter> {
+    pub fn swap_remove_in<B: GlobalAlloc>(
+        &mut self,
+        index: usize,
+        alloc: B,
+    ) -> Option<Imposter<B>> {
         if index >= self.len {
             return None;
         }
 
-        Some(unsafe { self.swap_remove_unchecked(index) })
+        Some(unsafe { self.swap_remove_unchecked_in(index, alloc) })
     }
 
-    /// Removes and returns the [`Imposter`] at `index`, swapping it with the last item in the vec
+    /// Removes and returns the [`Imposter`] at `index` allocated using `alloc`, swapping it with the last item in the vec
     ///
     /// # Safety
     /// `index` must be valid for this vec
     #[inline]
-    pub unsafe fn swap_remove_unchecked(&mut self, index: usize) -> Imposter {
+    pub unsafe fn swap_remove_unchecked_in<B: GlobalAlloc>(
+        &mut self,
+        index: usize,
+        alloc: B,
+    ) -> Imposter<B> {
         let imposter = {
             let last_index = self.len - 1;
             self.memory.swap_unchecked(index, last_index);
-            Imposter::from_raw(
-                self.memory.copy_to_alloc_unchecked(last_index),
-                self.typeid,
-                self.memory.element_layout(),
-                self.drop,
-            )
+            let src = self.memory.index_ptr_unchecked(last_index);
+            Imposter::from_raw_in(src, self.imposter_type(), alloc)
         };
 
         self.len -= 1;
@@ -226,33 +587,69 @@ impl ImposterVec {
         unsafe {
             let last_index = self.len - 1;
             self.memory.swap_unchecked(index, last_index);
-            let removed = self.memory.index_ptr_unchecked(last_index);
-            if let Some(drop) = self.drop {
-                (drop)(removed);
-            }
+            // shrink first so that a panicking drop never drops the item again
+            self.len = last_index;
+            self.memory.drop_range_unchecked(last_index, last_index + 1);
         }
-        self.len -= 1;
         true
     }
 
+    /// Appends the default value of the item type to the end of the vector, returning `true`
+    ///
+    /// Returns `false` if no default constructor was recorded for the item type.
+    /// See [`ImposterTypeBuilder::with_default`](crate::ImposterTypeBuilder::with_default).
+    #[inline]
+    pub fn push_default(&mut self) -> bool {
+        let len = self.len + 1;
+        self.resize_default(len)
+    }
+
+    /// Resizes the vector to `len` items, returning `true`
+    ///
+    /// New items are created using the recorded default constructor of the item type,
+    /// and extra items are dropped like [`ImposterVec::truncate`].
+    /// Returns `false` if the vec has to grow but no default constructor was recorded for the item type.
+    pub fn resize_default(&mut self, len: usize) -> bool {
+        if len <= self.len {
+            self.truncate(len);
+            return true;
+        }
+
+        let Some(default) = self.imposter_type().default_fn() else {
+            return false;
+        };
+
+        if len > self.memory.capacity() {
+            self.memory.resize(len);
+        }
+
+        while self.len < len {
+            unsafe { (default)(self.memory.index_ptr_unchecked(self.len)) };
+            // increment as we go so that a panicking constructor only drops initialized items
+            self.len += 1;
+        }
+
+        true
+    }
+
+    /// Shortens the vector to `len` items, calling the drop function of the removed items if necessary
+    ///
+    /// Has no effect if `len` is greater than or equal to the current length.
+    pub fn truncate(&mut self, len: usize) {
+        if len >= self.len {
+            return;
+        }
+
+        // shrink first so that a panicking drop never drops any item again
+        let old_len = self.len;
+        self.len = len;
+        unsafe { self.memory.drop_range_unchecked(len, old_len) };
+    }
+
     /// Clears all the elements in the vector, calling their drop function if necessary
     #[inline]
     pub fn clear(&mut self) {
-        match self.len {
-            0 => (),
-            len => unsafe {
-                self.len = 0;
-                if let Some(drop) = self.drop {
-                    let mut ptr = self.memory.ptr();
-                    let data_size = self.memory.element_layout().size();
-                    (drop)(ptr);
-                    for _ in 0..(len - 1) {
-                        ptr = ptr.add(data_size);
-                        (drop)(ptr);
-                    }
-                }
-            },
-        }
+        self.truncate(0)
     }
 
     /// Returns the number of items in the vec
@@ -270,44 +667,39 @@ impl ImposterVec {
     /// Returns true if `T` matches the internal item type
     #[inline]
     pub fn is_type<T: 'static>(&self) -> bool {
-        TypeId::of::<T>() != self.typeid
+        self.imposter_type().is::<T>()
     }
 
-    /// Converts this `ImposterVec` into a typed [`Vec`]
-    ///
-    /// Returns `Err(Self)` if `T` does not match this vec's type
+    /// Returns a [`TypeMismatch`] between this vecs type and `T`
     #[inline]
-    pub fn into_vec<T: 'static>(self) -> Result<Vec<T>, Self> {
-        if self.is_type::<T>() {
-            return Err(self);
-        }
-
-        Ok(unsafe { self.into_vec_unchecked() })
+    fn mismatch<T: 'static>(&self) -> TypeMismatch {
+        self.imposter_type().mismatch::<T>()
     }
 
-    /// Converts this `ImposterVec` into a typed [`Vec`]
-    ///
-    /// # Safety
-    /// type `T` must match this vecs type
+    /// Checks that `T` matches this vecs type and that `index` is in bounds
     #[inline]
-    pub unsafe fn into_vec_unchecked<T: 'static>(self) -> Vec<T> {
-        Vec::from_raw_parts(
-            self.memory.ptr() as *mut T,
-            self.len,
-            self.memory.capacity(),
-        )
+    fn check_access<T: 'static>(&self, index: usize) -> Result<(), ImposterError> {
+        if !self.is_type::<T>() {
+            return Err(self.mismatch::<T>().into());
+        }
+
+        if index >= self.len {
+            return Err(OutOfBounds::new(index, self.len).into());
+        }
+
+        Ok(())
     }
 
     /// Returns this vec as a reference to slice of type `T`
     ///
-    /// Returns `None` if `T` does not match this vecs type
+    /// Returns a [`TypeMismatch`] if `T` does not match this vecs type
     #[inline]
-    pub fn as_slice<T: 'static>(&self) -> Option<&[T]> {
-        if self.is_type::<T>() {
-            return None;
+    pub fn as_slice<T: 'static>(&self) -> Result<&[T], TypeMismatch> {
+        if !self.is_type::<T>() {
+            return Err(self.mismatch::<T>());
         }
 
-        Some(unsafe { self.as_slice_unchecked() })
+        Ok(unsafe { self.as_slice_unchecked() })
     }
 
     /// Returns this vec as a reference to slice of type `T`
@@ -315,20 +707,20 @@ impl ImposterVec {
     /// # Safety
     /// type `T` must match this vecs type
     #[inline]
-    pub unsafe fn as_slice_unchecked<T: 'static>(&self) -> &[T] {
+    pub unsafe fn as_slice_unchecked<T>(&self) -> &[T] {
         slice::from_raw_parts::<'_, T>(self.memory.ptr() as *const T, self.len)
     }
 
     /// Returns this vec as a mutable reference to slice of type `T`
     ///
-    /// Returns `None` if `T` does not match this vecs type
+    /// Returns a [`TypeMismatch`] if `T` does not match this vecs type
     #[inline]
-    pub fn as_slice_mut<T: 'static>(&mut self) -> Option<&mut [T]> {
-        if self.is_type::<T>() {
-            return None;
+    pub fn as_slice_mut<T: 'static>(&mut self) -> Result<&mut [T], TypeMismatch> {
+        if !self.is_type::<T>() {
+            return Err(self.mismatch::<T>());
         }
 
-        Some(unsafe { self.as_slice_mut_unchecked() })
+        Ok(unsafe { self.as_slice_mut_unchecked() })
     }
 
     /// Returns this vec as a mutable reference to slice of type `T`
@@ -336,20 +728,20 @@ impl ImposterVec {
     /// # Safety
     /// type `T` must match this vecs type
     #[inline]
-    pub unsafe fn as_slice_mut_unchecked<T: 'static>(&mut self) -> &mut [T] {
+    pub unsafe fn as_slice_mut_unchecked<T>(&mut self) -> &mut [T] {
         slice::from_raw_parts_mut::<'_, T>(self.memory.ptr() as *mut T, self.len)
     }
 
     /// Returns this vec as a pointer to a slice of type `T`
     ///
-    /// Returns `None` if `T` does not match this vecs type
+    /// Returns a [`TypeMismatch`] if `T` does not match this vecs type
     #[inline]
-    pub fn as_slice_ptr<T: 'static>(&self) -> Option<ptr::NonNull<[T]>> {
-        if self.is_type::<T>() {
-            return None;
+    pub fn as_slice_ptr<T: 'static>(&self) -> Result<ptr::NonNull<[T]>, TypeMismatch> {
+        if !self.is_type::<T>() {
+            return Err(self.mismatch::<T>());
         }
 
-        Some(unsafe { self.as_slice_ptr_unchecked() })
+        Ok(unsafe { self.as_slice_ptr_unchecked() })
     }
 
     /// Returns this vec as a pointer to a slice of type `T`
@@ -357,47 +749,197 @@ impl ImposterVec {
     /// # Safety
     /// type `T` must match this vecs type
     #[inline]
-    pub unsafe fn as_slice_ptr_unchecked<T: 'static>(&self) -> ptr::NonNull<[T]> {
+    pub unsafe fn as_slice_ptr_unchecked<T>(&self) -> ptr::NonNull<[T]> {
         let slice = slice::from_raw_parts_mut::<'_, T>(self.memory.ptr() as *mut T, self.len);
         ptr::NonNull::new_unchecked(slice as *mut [T])
     }
 
     /// Returns an iterator over all the elements of this vec
     ///
-    /// This iterator will use untyped pointer references to each item.
+    /// This iterator will yield an [`ImposterRef`] to each item.
     /// If you want a typed iterater, first use `as_slice<T>` or `as_slice_mut<T>` and iterate over the slice instead.
     #[inline]
-    pub fn iter(&self) -> Iter {
+    pub fn iter(&self) -> Iter<'_> {
         Iter::new(self)
     }
+
+    /// Returns an iterator over all the elements of this vec that yields an [`ImposterMut`] to each item
+    #[inline]
+    pub fn iter_mut(&mut self) -> IterMut<'_> {
+        IterMut::new(self)
+    }
+
+    /// Returns an iterator over all the elements of this vec viewed as the trait object `&Dyn`
+    ///
+    /// Returns `None` if no [`DynCaster`] for `Dyn` was registered with the item type.
+    #[inline]
+    pub fn iter_dyn<Dyn: ?Sized + 'static>(&self) -> Option<DynIter<'_, Dyn>> {
+        let caster = self.imposter_type().dyn_caster::<Dyn>()?;
+        Some(DynIter {
+            iter: self.iter(),
+            caster,
+        })
+    }
 }
 
-/// An iterator over the raw pointers in a [`ImposterVec`]
-pub struct Iter<'a> {
-    vec: &'a ImposterVec,
+/// Walks the raw item pointers of a [`ImposterVec`], shared by its iterators
+struct RawIter {
+    ptr: *mut u8,
+    ty: &'static ImposterType,
     index: usize,
+    len: usize,
+}
+
+impl RawIter {
+    #[inline]
+    fn new<A: GlobalAlloc>(vec: &ImposterVec<A>) -> Self {
+        Self {
+            ptr: vec.memory.ptr(),
+            ty: vec.imposter_type(),
+            index: 0,
+            len: vec.len,
+        }
+    }
+
+    #[inline]
+    fn next(&mut self) -> Option<ptr::NonNull<u8>> {
+        if self.index >= self.len {
+            return None;
+        }
+
+        // SAFETY:
+        // the index is in bounds, and the vec pointer is never null
+        let ptr = unsafe {
+            let offset = self.index * self.ty.layout().size();
+            ptr::NonNull::new_unchecked(self.ptr.add(offset))
+        };
+        self.index += 1;
+        Some(ptr)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.len - self.index;
+        (len, Some(len))
+    }
+}
+
+/// An iterator over [`ImposterRef`]s to the items in a [`ImposterVec`]
+pub struct Iter<'a> {
+    raw: RawIter,
+    _vec: PhantomData<&'a ()>,
 }
 
 impl<'a> Iter<'a> {
-    fn new(vec: &'a ImposterVec) -> Self {
-        Self { vec, index: 0 }
+    fn new<A: GlobalAlloc>(vec: &'a ImposterVec<A>) -> Self {
+        Self {
+            raw: RawIter::new(vec),
+            _vec: PhantomData,
+        }
     }
 }
 
 impl<'a> Iterator for Iter<'a> {
-    type Item = *mut u8;
+    type Item = ImposterRef<'a>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.index >= self.vec.len() {
-            return None;
+        let ptr = self.raw.next()?;
+        Some(unsafe { ImposterRef::from_raw(ptr, self.raw.ty) })
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.raw.size_hint()
+    }
+}
+
+impl ExactSizeIterator for Iter<'_> {}
+
+impl<'a, A: GlobalAlloc> IntoIterator for &'a ImposterVec<A> {
+    type Item = ImposterRef<'a>;
+    type IntoIter = Iter<'a>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// An iterator over [`ImposterMut`]s to the items in a [`ImposterVec`]
+pub struct IterMut<'a> {
+    raw: RawIter,
+    _vec: PhantomData<&'a mut ()>,
+}
+
+impl<'a> IterMut<'a> {
+    fn new<A: GlobalAlloc>(vec: &'a mut ImposterVec<A>) -> Self {
+        Self {
+            raw: RawIter::new(vec),
+            _vec: PhantomData,
         }
+    }
+}
+
+impl<'a> Iterator for IterMut<'a> {
+    type Item = ImposterMut<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let ptr = self.raw.next()?;
+        // SAFETY:
+        // each item is only yielded once, so the mutable borrows never overlap
+        Some(unsafe { ImposterMut::from_raw(ptr, self.raw.ty) })
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.raw.size_hint()
+    }
+}
+
+impl ExactSizeIterator for IterMut<'_> {}
+
+impl<'a, A: GlobalAlloc> IntoIterator for &'a mut ImposterVec<A> {
+    type Item = ImposterMut<'a>;
+    type IntoIter = IterMut<'a>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+/// An iterator over the items in a [`ImposterVec`] viewed as the trait object `&Dyn`
+pub struct DynIter<'a, Dyn: ?Sized + 'static> {
+    iter: Iter<'a>,
+    caster: &'static DynCaster<Dyn>,
+}
+
+impl<'a, Dyn: ?Sized + 'static> Iterator for DynIter<'a, Dyn> {
+    type Item = &'a Dyn;
 
-        Some(unsafe { self.vec.memory.index_ptr_unchecked(self.index) })
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let item = self.iter.next()?;
+        Some(unsafe { self.caster.cast_ref(item.as_ptr()) })
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
     }
 }
 
+impl<Dyn: ?Sized + 'static> ExactSizeIterator for DynIter<'_, Dyn> {}
+
 #[cfg(test)]
 mod tests {
+    use std::{
+        panic::{self, AssertUnwindSafe},
+        sync::LazyLock,
+    };
+
+    use droptest::{assert_drop_stats, DropGuard, DropRegistry};
+
     use super::*;
 
     #[derive(Debug)]
@@ -411,6 +953,13 @@ mod tests {
         assert!(vec.len() == 1);
     }
 
+    #[test]
+    fn imposter_vec_type_name() {
+        let vec = ImposterVec::new::<Test1>();
+        assert_eq!(vec.type_name(), std::any::type_name::<Test1>());
+        assert!(format!("{vec:?}").contains(std::any::type_name::<Test1>()));
+    }
+
     #[test]
     fn push_imposter_vec() {
         let mut vec = ImposterVec::new::<Test1>();
@@ -419,6 +968,62 @@ mod tests {
         assert!(vec.len() == 2);
     }
 
+    #[test]
+    fn push_mismatch_vec() {
+        let mut vec = ImposterVec::new::<Test1>();
+        let error = vec.push_item(42u32).unwrap_err();
+        assert_eq!(error.error().expected(), std::any::type_name::<Test1>());
+        assert_eq!(error.error().found(), std::any::type_name::<u32>());
+        assert_eq!(error.into_value(), 42);
+
+        let error = vec.push_imposter(Imposter::new(42u32)).unwrap_err();
+        assert_eq!(error.into_value().downcast::<u32>().unwrap(), 42);
+        assert!(vec.is_empty());
+    }
+
+    #[test]
+    fn get_errors_vec() {
+        let mut vec = ImposterVec::new::<Test1>();
+        vec.push_item(Test1(42)).unwrap();
+        assert_eq!(vec.get::<Test1>(0).unwrap().0, 42);
+        assert!(matches!(
+            vec.get::<u32>(0),
+            Err(ImposterError::TypeMismatch(_))
+        ));
+        assert_eq!(
+            vec.get_mut::<Test1>(1).unwrap_err(),
+            ImposterError::OutOfBounds(OutOfBounds::new(1, 1))
+        );
+        assert!(vec.as_slice::<u32>().is_err());
+    }
+
+    #[test]
+    fn clone_vec() {
+        let mut vec = ImposterVec::new::<String>();
+        vec.push_item(String::from("sus")).unwrap();
+        assert!(vec.try_clone().is_none());
+
+        let mut vec = ImposterVec::new_cloneable::<String>();
+        vec.push_item(String::from("red")).unwrap();
+        vec.push_item(String::from("sus")).unwrap();
+        let clone = vec.try_clone().unwrap();
+        drop(vec);
+        assert_eq!(clone.as_slice::<String>().unwrap(), ["red", "sus"]);
+
+        let vec = ImposterVec::from_imposter(Imposter::new_cloneable(42u32));
+        assert_eq!(vec.try_clone().unwrap().get::<u32>(0).unwrap(), &42);
+    }
+
+    #[test]
+    fn debug_vec() {
+        let mut vec = ImposterVec::new_debuggable::<Test1>();
+        vec.push_item(Test1(42)).unwrap();
+        vec.push_item(Test1(43)).unwrap();
+        assert_eq!(format!("{vec:?}"), "[Test1(42), Test1(43)]");
+        assert_eq!(format!("{:?}", vec.get_debug(1).unwrap()), "Test1(43)");
+        assert!(vec.get_debug(2).is_none());
+    }
+
     #[test]
     fn swap_drop_vec() {
         let mut vec = ImposterVec::from_imposter(Imposter::new(Test1(42)));
@@ -431,7 +1036,7 @@ mod tests {
         vec.swap_drop(0);
         assert!(vec.len() == 1);
         vec.swap_drop(0);
-        assert!(vec.len() == 0);
+        assert!(vec.is_empty());
     }
 
     #[test]
@@ -447,4 +1052,230 @@ mod tests {
         let test = vec.swap_remove(0).unwrap().downcast::<Test1>().unwrap();
         assert!(test.0 == 44);
     }
+
+    #[test]
+    fn swap_remove_heap_vec() {
+        let mut vec = ImposterVec::new::<String>();
+        vec.push_item(String::from("imposter")).unwrap();
+        vec.push_item(String::from("crewmate")).unwrap();
+        let imposter = vec.swap_remove(0).unwrap();
+        assert!(!imposter.is_inline());
+        assert_eq!(imposter.downcast::<String>().unwrap(), "imposter");
+        assert_eq!(vec.get::<String>(0).unwrap(), "crewmate");
+    }
+
+    #[test]
+    fn unsized_vec() {
+        let mut vec = ImposterVec::from_imposter(Imposter::from(Box::<str>::from("red")));
+        vec.push_imposter(Imposter::from(Box::<str>::from("sus")))
+            .unwrap();
+        assert!(vec.push_imposter(Imposter::from(Box::new(42u8))).is_err());
+        assert_eq!(&**vec.get::<Box<str>>(1).unwrap(), "sus");
+    }
+
+    #[test]
+    fn iter_vec() {
+        let mut vec = ImposterVec::new::<Test1>();
+        vec.push_item(Test1(42)).unwrap();
+        vec.push_item(Test1(43)).unwrap();
+        for mut item in vec.iter_mut() {
+            item.downcast_mut::<Test1>().unwrap().0 += 1;
+        }
+
+        let items: Vec<u32> = vec
+            .iter()
+            .map(|item| item.downcast_ref::<Test1>().unwrap().0)
+            .collect();
+        assert_eq!(items, [43, 44]);
+        assert_eq!(vec.iter().len(), 2);
+    }
+
+    #[test]
+    fn default_vec() {
+        static TYPE: ImposterType = ImposterType::builder::<String>().with_default().build();
+
+        let mut vec = ImposterVec::with_type(&TYPE);
+        vec.push_item(String::from("sus")).unwrap();
+        assert!(vec.push_default());
+        assert!(vec.resize_default(4));
+        assert_eq!(vec.as_slice::<String>().unwrap(), ["sus", "", "", ""]);
+        assert!(vec.resize_default(1));
+        assert_eq!(vec.as_slice::<String>().unwrap(), ["sus"]);
+
+        let mut vec = ImposterVec::new::<String>();
+        assert!(!vec.push_default());
+        assert!(vec.resize_default(0));
+    }
+
+    #[test]
+    fn dedupe_vec() {
+        static TYPE: ImposterType = ImposterType::builder::<String>().with_hash().build();
+
+        let mut vec = ImposterVec::with_type(&TYPE);
+        for word in ["red", "sus", "red"] {
+            vec.push_item(String::from(word)).unwrap();
+        }
+
+        let unique: std::collections::HashSet<_> =
+            vec.iter().map(|item| item.into_key().unwrap()).collect();
+        assert_eq!(unique.len(), 2);
+        assert_eq!(vec.get_ref(0), vec.get_ref(2));
+        assert_ne!(vec.get_ref(0), vec.get_ref(1));
+    }
+
+    #[test]
+    fn ref_vec() {
+        let mut vec = ImposterVec::new_cloneable::<String>();
+        vec.push_item(String::from("sus")).unwrap();
+        assert!(vec.get_ref(1).is_none());
+
+        let item = vec.get_ref(0).unwrap();
+        assert_eq!(item.type_name(), std::any::type_name::<String>());
+        assert_eq!(item.layout(), std::alloc::Layout::new::<String>());
+        assert!(item.downcast_ref::<u32>().is_none());
+        let clone = item.try_clone().unwrap();
+
+        let mut item = vec.get_mut_ref(0).unwrap();
+        item.downcast_mut::<String>().unwrap().push_str("picious");
+        let item = item.into_mut::<u32>().unwrap_err();
+        assert_eq!(item.into_mut::<String>().unwrap(), "suspicious");
+        assert_eq!(clone.downcast::<String>().unwrap(), "sus");
+    }
+
+    #[test]
+    fn dyn_vec() {
+        static TYPE: ImposterType = ImposterType::builder::<u32>()
+            .with_dyn(&[crate::dyn_cast!(u32 as dyn fmt::Display)])
+            .build();
+
+        let mut vec = ImposterVec::with_type(&TYPE);
+        vec.push_item(42u32).unwrap();
+        vec.push_item(43u32).unwrap();
+        let items: Vec<String> = vec
+            .iter_dyn::<dyn fmt::Display>()
+            .unwrap()
+            .map(|item| item.to_string())
+            .collect();
+        assert_eq!(items, ["42", "43"]);
+        let mut iter = vec.iter_dyn::<dyn fmt::Display>().unwrap();
+        assert_eq!(iter.len(), 2);
+        iter.next();
+        assert_eq!(iter.size_hint(), (1, Some(1)));
+        assert!(vec.get_dyn::<dyn fmt::Display>(2).is_none());
+        assert!(vec.get_dyn_mut::<dyn fmt::Debug>(0).is_none());
+        assert!(vec.iter_dyn::<dyn fmt::Debug>().is_none());
+    }
+
+    #[test]
+    fn registered_vec() {
+        let mut registry = TypeRegistry::new();
+        registry.register_type("name", ImposterType::cloneable::<String>());
+        assert!(ImposterVec::from_registered(&registry, "missing").is_none());
+
+        let mut vec = ImposterVec::from_registered(&registry, "name").unwrap();
+        vec.push_item(String::from("sus")).unwrap();
+        let clone = vec.try_clone().unwrap();
+        assert_eq!(clone.as_slice::<String>().unwrap(), ["sus"]);
+    }
+
+    #[test]
+    fn field_vec() {
+        #[derive(Debug)]
+        struct Health {
+            current: u32,
+            max: u32,
+        }
+
+        static HEALTH: ImposterType = ImposterType::builder::<Health>()
+            .with_fields(crate::imposter_fields!(Health {
+                current: u32,
+                max: u32
+            }))
+            .build();
+
+        let mut vec = ImposterVec::with_type(&HEALTH);
+        vec.push_item(Health {
+            current: 5,
+            max: 10,
+        })
+        .unwrap();
+        vec.push_item(Health { current: 1, max: 3 }).unwrap();
+        for mut item in &mut vec {
+            let max = *item.field("max").unwrap().downcast_ref::<u32>().unwrap();
+            let mut current = item.field_mut("current").unwrap();
+            *current.downcast_mut::<u32>().unwrap() = max;
+        }
+
+        let health = vec.get_ref(1).unwrap();
+        assert_eq!(health.field("current").unwrap().downcast_ref(), Some(&3u32));
+        assert!(health.field("missing").is_none());
+    }
+
+    /// Panics when dropped if `explode` is set
+    struct Bomb {
+        _guard: DropGuard<'static, ()>,
+        explode: bool,
+    }
+
+    impl Drop for Bomb {
+        fn drop(&mut self) {
+            if self.explode {
+                panic!("boom");
+            }
+        }
+    }
+
+    #[test]
+    fn panic_drop_vec() {
+        // bombs are stored in an imposter vec, so the registry they borrow must be static
+        static REGISTRY: LazyLock<DropRegistry> = LazyLock::new(DropRegistry::default);
+        let registry = &*REGISTRY;
+        let bomb = |explode| Bomb {
+            _guard: registry.new_guard(),
+            explode,
+        };
+
+        let mut vec = ImposterVec::new::<Bomb>();
+        for index in 0..5 {
+            vec.push_item(bomb(index == 1)).unwrap();
+        }
+
+        let result = panic::catch_unwind(AssertUnwindSafe(|| vec.truncate(1)));
+        assert!(result.is_err());
+        assert_eq!(vec.len(), 1);
+        assert_drop_stats!(registry, { created: 5, dropped: 4 });
+
+        vec.push_item(bomb(true)).unwrap();
+        vec.push_item(bomb(false)).unwrap();
+        let result = panic::catch_unwind(AssertUnwindSafe(|| vec.swap_drop(1)));
+        assert!(result.is_err());
+        assert_eq!(vec.len(), 2);
+        assert_drop_stats!(registry, { created: 7, dropped: 5 });
+
+        vec.push_item(bomb(true)).unwrap();
+        vec.push_item(bomb(false)).unwrap();
+        let result = panic::catch_unwind(AssertUnwindSafe(move || drop(vec)));
+        assert!(result.is_err());
+        assert_drop_stats!(registry, { created: 9, dropped: 9 });
+    }
+
+    #[test]
+    fn reserve_vec() {
+        let mut vec = ImposterVec::new::<u64>();
+        vec.try_reserve(3).unwrap();
+        assert_eq!(vec.capacity(), 3);
+        vec.try_push_item(1u64).unwrap();
+        vec.reserve(3);
+        assert_eq!(vec.capacity(), 6);
+
+        let overflow = Err(TryReserveError::CapacityOverflow);
+        assert_eq!(vec.try_reserve(usize::MAX), overflow);
+        assert_eq!(vec.try_reserve(isize::MAX as usize / 8), overflow);
+        assert_eq!(vec.capacity(), 6);
+        assert_eq!(vec.as_slice::<u64>().unwrap(), [1]);
+
+        let mut vec = ImposterVec::new::<()>();
+        assert_eq!(vec.try_reserve(usize::MAX), Ok(()));
+        assert_eq!(vec.capacity(), usize::MAX);
+    }
 }
@@ -0,0 +1,86 @@
+use std::{
+    any::{Any, TypeId},
+    mem,
+};
+
+/// A table of [`DynCaster`]s registered for a type, see [`ImposterTypeBuilder::with_dyn`](crate::ImposterTypeBuilder::with_dyn)
+pub type DynCasts = &'static [&'static (dyn Any + Send + Sync)];
+
+/// Casts type erased data of a single type into the trait object `Dyn`
+///
+/// Casters are usually created using the [`dyn_cast`](crate::dyn_cast) macro.
+pub struct DynCaster<Dyn: ?Sized + 'static> {
+    type_id: TypeId,
+    cast_ref: unsafe fn(*const u8) -> *const Dyn,
+    cast_mut: unsafe fn(*mut u8) -> *mut Dyn,
+}
+
+impl<Dyn: ?Sized + 'static> DynCaster<Dyn> {
+    /// Creates a new caster that converts data of type `T` into `Dyn`
+    #[inline]
+    pub const fn new<T: 'static>(
+        cast_ref: fn(&T) -> &Dyn,
+        cast_mut: fn(&mut T) -> &mut Dyn,
+    ) -> Self {
+        // SAFETY:
+        // references and raw pointers with the same metadata are ABI compatible,
+        // so these function pointers may be called with the erased signature
+        unsafe {
+            Self {
+                type_id: TypeId::of::<T>(),
+                cast_ref: mem::transmute::<fn(&T) -> &Dyn, unsafe fn(*const u8) -> *const Dyn>(
+                    cast_ref,
+                ),
+                cast_mut: mem::transmute::<fn(&mut T) -> &mut Dyn, unsafe fn(*mut u8) -> *mut Dyn>(
+                    cast_mut,
+                ),
+            }
+        }
+    }
+
+    /// Returns the [`TypeId`] of the type this caster converts from
+    #[inline]
+    pub fn type_id(&self) -> TypeId {
+        self.type_id
+    }
+
+    /// Casts the data at `ptr` into `&Dyn`
+    ///
+    /// # Safety
+    /// `ptr` must point to a valid value of the type this caster was created for
+    #[inline]
+    pub unsafe fn cast_ref<'a>(&self, ptr: *const u8) -> &'a Dyn {
+        &*(self.cast_ref)(ptr)
+    }
+
+    /// Casts the data at `ptr` into `&mut Dyn`
+    ///
+    /// # Safety
+    /// `ptr` must point to a valid value of the type this caster was created for,
+    /// and must be valid for writes
+    #[inline]
+    pub unsafe fn cast_mut<'a>(&self, ptr: *mut u8) -> &'a mut Dyn {
+        &mut *(self.cast_mut)(ptr)
+    }
+}
+
+/// Creates a `&'static` [`DynCaster`] that casts type `T` into the trait object `Dyn`
+///
+/// # Example
+/// ```
+/// use std::fmt::{Debug, Display};
+/// use imposters::{dyn_cast, Imposter, ImposterType};
+///
+/// static TYPE: ImposterType = ImposterType::builder::<u32>()
+///     .with_dyn(&[dyn_cast!(u32 as dyn Display), dyn_cast!(u32 as dyn Debug)])
+///     .build();
+///
+/// let imposter = Imposter::with_type(42u32, &TYPE).unwrap();
+/// assert_eq!(imposter.as_dyn::<dyn Display>().unwrap().to_string(), "42");
+/// ```
+#[macro_export]
+macro_rules! dyn_cast {
+    ($ty:ty as $dyn:ty) => {
+        &$crate::DynCaster::<$dyn>::new::<$ty>(|item| item, |item| item)
+    };
+}
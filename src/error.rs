@@ -0,0 +1,294 @@
+use std::{alloc::Layout, error::Error, fmt, io};
+
+/// Error describing a type that does not match the type of an imposter or collection
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TypeMismatch {
+    expected: &'static str,
+    found: &'static str,
+}
+
+impl fmt::Display for TypeMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "type mismatch: expected `{}`, found `{}`",
+            self.expected, self.found
+        )
+    }
+}
+
+impl Error for TypeMismatch {}
+
+impl TypeMismatch {
+    #[inline]
+    pub(crate) fn new(expected: &'static str, found: &'static str) -> Self {
+        Self { expected, found }
+    }
+
+    /// Returns the name of the type stored in the imposter or collection
+    #[inline]
+    pub fn expected(&self) -> &'static str {
+        self.expected
+    }
+
+    /// Returns the name of the type that was supplied
+    #[inline]
+    pub fn found(&self) -> &'static str {
+        self.found
+    }
+}
+
+/// Error describing an index that is out of bounds for a collection
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OutOfBounds {
+    index: usize,
+    len: usize,
+}
+
+impl fmt::Display for OutOfBounds {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "index out of bounds: the len is {} but the index is {}",
+            self.len, self.index
+        )
+    }
+}
+
+impl Error for OutOfBounds {}
+
+impl OutOfBounds {
+    #[inline]
+    pub(crate) fn new(index: usize, len: usize) -> Self {
+        Self { index, len }
+    }
+
+    /// Returns the index that was accessed
+    #[inline]
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Returns the length of the collection at the time of access
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the collection was empty at the time of access
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+/// Error returned when accessing an item of a collection fails
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImposterError {
+    TypeMismatch(TypeMismatch),
+    OutOfBounds(OutOfBounds),
+}
+
+impl fmt::Display for ImposterError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TypeMismatch(e) => e.fmt(f),
+            Self::OutOfBounds(e) => e.fmt(f),
+        }
+    }
+}
+
+impl Error for ImposterError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::TypeMismatch(e) => Some(e),
+            Self::OutOfBounds(e) => Some(e),
+        }
+    }
+}
+
+impl From<TypeMismatch> for ImposterError {
+    #[inline]
+    fn from(error: TypeMismatch) -> Self {
+        Self::TypeMismatch(error)
+    }
+}
+
+impl From<OutOfBounds> for ImposterError {
+    #[inline]
+    fn from(error: OutOfBounds) -> Self {
+        Self::OutOfBounds(error)
+    }
+}
+
+/// Error returned when a value is rejected because of an error `E`, which defaults to a [`TypeMismatch`]
+///
+/// Ownership of the rejected value is handed back and can be retrieved using [`Rejected::into_value`]
+pub struct Rejected<T, E = TypeMismatch> {
+    value: T,
+    error: E,
+}
+
+impl<T, E: fmt::Debug> fmt::Debug for Rejected<T, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Rejected")
+            .field("error", &self.error)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T, E: fmt::Display> fmt::Display for Rejected<T, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.error.fmt(f)
+    }
+}
+
+impl<T, E: Error + 'static> Error for Rejected<T, E> {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+impl<T, E> Rejected<T, E> {
+    #[inline]
+    pub(crate) fn new(value: T, error: E) -> Self {
+        Self { value, error }
+    }
+
+    /// Returns the error that caused the value to be rejected
+    #[inline]
+    pub fn error(&self) -> E
+    where
+        E: Copy,
+    {
+        self.error
+    }
+
+    /// Returns a reference to the rejected value
+    #[inline]
+    pub fn value(&self) -> &T {
+        &self.value
+    }
+
+    /// Consumes the error, returning the rejected value
+    #[inline]
+    pub fn into_value(self) -> T {
+        self.value
+    }
+
+    /// Consumes the error, returning the rejected value and the error that caused it
+    #[inline]
+    pub fn into_parts(self) -> (T, E) {
+        (self.value, self.error)
+    }
+}
+
+/// Error returned when memory for a collection could not be reserved
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TryReserveError {
+    /// The requested capacity exceeds the maximum size of an allocation
+    CapacityOverflow,
+    /// The allocator failed to allocate memory with the given layout
+    AllocError(Layout),
+}
+
+impl fmt::Display for TryReserveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("memory allocation failed")?;
+        match self {
+            Self::CapacityOverflow => f.write_str(" because the capacity exceeded the maximum"),
+            Self::AllocError(_) => f.write_str(" because the allocator returned an error"),
+        }
+    }
+}
+
+impl Error for TryReserveError {}
+
+/// Error returned when pushing a value into a collection fails
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PushError {
+    TypeMismatch(TypeMismatch),
+    TryReserve(TryReserveError),
+}
+
+impl fmt::Display for PushError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TypeMismatch(e) => e.fmt(f),
+            Self::TryReserve(e) => e.fmt(f),
+        }
+    }
+}
+
+impl Error for PushError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::TypeMismatch(e) => Some(e),
+            Self::TryReserve(e) => Some(e),
+        }
+    }
+}
+
+impl From<TypeMismatch> for PushError {
+    #[inline]
+    fn from(error: TypeMismatch) -> Self {
+        Self::TypeMismatch(error)
+    }
+}
+
+impl From<TryReserveError> for PushError {
+    #[inline]
+    fn from(error: TryReserveError) -> Self {
+        Self::TryReserve(error)
+    }
+}
+
+/// Error returned when serializing or deserializing using a [`TypeRegistry`](crate::TypeRegistry) fails
+#[derive(Debug)]
+pub enum SerializeError {
+    /// The underlying reader or writer failed, or the data is malformed
+    Io(io::Error),
+    /// The data contains a type name that is not registered
+    UnknownName(String),
+    /// The type was not registered with serialization functions
+    Unregistered(&'static str),
+    /// The memory for the deserialized items could not be reserved
+    TryReserve(TryReserveError),
+}
+
+impl fmt::Display for SerializeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(e) => e.fmt(f),
+            Self::UnknownName(name) => write!(f, "unknown type name `{name}`"),
+            Self::Unregistered(name) => {
+                write!(f, "type `{name}` is not registered for serialization")
+            }
+            Self::TryReserve(e) => e.fmt(f),
+        }
+    }
+}
+
+impl Error for SerializeError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Io(e) => Some(e),
+            Self::TryReserve(e) => Some(e),
+            Self::UnknownName(_) | Self::Unregistered(_) => None,
+        }
+    }
+}
+
+impl From<io::Error> for SerializeError {
+    #[inline]
+    fn from(error: io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+impl From<TryReserveError> for SerializeError {
+    #[inline]
+    fn from(error: TryReserveError) -> Self {
+        Self::TryReserve(error)
+    }
+}
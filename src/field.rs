@@ -0,0 +1,131 @@
+use crate::{ImposterType, ImposterTypeId};
+
+/// A table of [`ImposterField`]s registered for a type, see [`ImposterTypeBuilder::with_fields`](crate::ImposterTypeBuilder::with_fields)
+pub type ImposterFields = &'static [ImposterField];
+
+/// Describes a single field of a struct, so that it can be reached inside type erased data
+///
+/// Fields are usually described using the [`imposter_fields`](crate::imposter_fields) macro.
+#[derive(Debug, Clone, Copy)]
+pub struct ImposterField {
+    name: &'static str,
+    offset: usize,
+    ty: &'static ImposterType,
+    type_id: ImposterTypeId,
+    parent_id: ImposterTypeId,
+}
+
+impl ImposterField {
+    /// Creates a new field of type `F` called `name` inside the struct `P`, that is described by `ty`
+    ///
+    /// If `ty` does not describe `F`, the field can not be reached through its descriptor.
+    ///
+    /// # Safety
+    /// Every value of type `P` must contain a valid `F` at `offset`
+    #[inline]
+    pub const unsafe fn new<P: 'static, F: 'static>(
+        name: &'static str,
+        offset: usize,
+        ty: &'static ImposterType,
+    ) -> Self {
+        Self {
+            name,
+            offset,
+            ty,
+            type_id: ImposterTypeId::of::<F>(),
+            parent_id: ImposterTypeId::of::<P>(),
+        }
+    }
+
+    /// Returns the name of this field
+    #[inline]
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Returns the id of the struct that owns this field
+    #[inline]
+    pub fn parent_id(&self) -> ImposterTypeId {
+        self.parent_id
+    }
+
+    /// Returns the offset of this field in bytes from the start of the struct that owns it
+    #[inline]
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Returns the [`ImposterType`] describing this field as `Some`
+    ///
+    /// Returns `None` if the descriptor this field was created with does not describe the field type.
+    #[inline]
+    pub fn imposter_type(&self) -> Option<&'static ImposterType> {
+        (self.ty.type_id() == self.type_id).then_some(self.ty)
+    }
+
+    /// Returns this field offset by the position of the `parent` field that contains it
+    #[inline]
+    pub(crate) fn nested_in(self, parent: &Self) -> Self {
+        Self {
+            offset: parent.offset + self.offset,
+            parent_id: parent.parent_id,
+            ..self
+        }
+    }
+}
+
+/// Creates the `&'static` [`ImposterFields`] table that describes the fields of a struct
+///
+/// Each field is written as `name: Type`, using [`ImposterType::of`] to describe the field.
+/// Fields that have fields of their own may be written as `name: Type = &DESCRIPTOR`,
+/// so that the fields of `DESCRIPTOR` can be reached using a path like `position.x`.
+///
+/// # Example
+/// ```
+/// use imposters::{imposter_fields, Imposter, ImposterType};
+///
+/// struct Vec2 {
+///     x: f32,
+///     y: f32,
+/// }
+///
+/// struct Transform {
+///     position: Vec2,
+///     rotation: f32,
+/// }
+///
+/// static VEC2: ImposterType = ImposterType::builder::<Vec2>()
+///     .with_fields(imposter_fields!(Vec2 { x: f32, y: f32 }))
+///     .build();
+///
+/// static TRANSFORM: ImposterType = ImposterType::builder::<Transform>()
+///     .with_fields(imposter_fields!(Transform { position: Vec2 = &VEC2, rotation: f32 }))
+///     .build();
+///
+/// let transform = Transform { position: Vec2 { x: 1., y: 2. }, rotation: 0. };
+/// let mut imposter = Imposter::with_type(transform, &TRANSFORM).unwrap();
+/// *imposter.field_mut("position.x").unwrap().downcast_mut::<f32>().unwrap() = 3.;
+/// assert_eq!(imposter.field("position.x").unwrap().downcast_ref::<f32>(), Some(&3.));
+/// ```
+#[macro_export]
+macro_rules! imposter_fields {
+    ($parent:ty { $($field:ident: $ty:ty $(= $desc:expr)?),* $(,)? }) => {
+        &[$(
+            const {
+                // ensures that the field exists with the given type
+                let _: fn(&$parent) -> &$ty = |parent| &parent.$field;
+                let offset = ::core::mem::offset_of!($parent, $field);
+                let ty: &'static $crate::ImposterType = $crate::imposter_fields!(@type $ty $(, $desc)?);
+                // SAFETY:
+                // the offset is taken from the field that is checked above
+                unsafe { $crate::ImposterField::new::<$parent, $ty>(stringify!($field), offset, ty) }
+            }
+        ),*]
+    };
+    (@type $ty:ty) => {
+        $crate::ImposterType::of::<$ty>()
+    };
+    (@type $ty:ty, $desc:expr) => {
+        $desc
+    };
+}
@@ -1,33 +1,105 @@
 use std::{
-    alloc::{dealloc, Layout},
-    any::TypeId,
-    mem, ptr,
+    alloc::{handle_alloc_error, GlobalAlloc, Layout},
+    cmp::Ordering,
+    convert::Infallible,
+    fmt,
+    hash::{Hash, Hasher},
+    mem::{self, ManuallyDrop, MaybeUninit},
+    ptr,
 };
 
-pub type ImposterDrop = unsafe fn(ptr: *mut u8);
+use crate::{
+    memory::{create_dangling_ptr, DeallocGuard},
+    ErasedDebug, Global, ImposterDrop, ImposterKey, ImposterMut, ImposterRef, ImposterType,
+    ImposterTypeId, PinnedImposter, Rejected,
+};
 
 /// # ඞ IMPOSTER ඞ
 ///
 /// A type erased wrapper around any kind of data
-#[derive(Debug)]
-pub struct Imposter {
-    data: ptr::NonNull<u8>,
-    typeid: TypeId,
-    layout: Layout,
-    drop: Option<ImposterDrop>,
+///
+/// Values that fit inside a single pointer (see [`ImposterType::is_inline`])
+/// are stored inline inside the imposter, all other values are stored on the heap
+/// using the allocator `A`, which defaults to the [`Global`] allocator.
+///
+/// Imposters may be compared, hashed and ordered if the matching functions
+/// were recorded in their [`ImposterType`], see [`ImposterRef`] for details.
+///
+/// Everything known about the internal type lives in its `'static` [`ImposterType`],
+/// so with the default allocator an imposter is only two pointers wide,
+/// and `Option<Imposter>` has the same size as `Imposter`.
+pub struct Imposter<A: GlobalAlloc = Global> {
+    data: ImposterData,
+    ty: &'static ImposterType,
+    alloc: A,
+}
+
+/// Storage for the data of an imposter
+///
+/// Only `inline` is valid if the imposter type is inline, otherwise only `heap` is valid.
+#[derive(Clone, Copy)]
+pub(crate) union ImposterData {
+    heap: ptr::NonNull<u8>,
+    inline: MaybeUninit<usize>,
 }
 
-impl Drop for Imposter {
+impl ImposterData {
+    #[inline]
+    const fn uninit() -> Self {
+        Self {
+            inline: MaybeUninit::uninit(),
+        }
+    }
+}
+
+impl<A: GlobalAlloc> fmt::Debug for Imposter<A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.ty.debug_fn().is_some() {
+            return self.debug_value().fmt(f);
+        }
+
+        f.debug_struct("Imposter")
+            .field("type_name", &self.type_name())
+            .field("data", &self.data())
+            .finish()
+    }
+}
+
+impl<A: GlobalAlloc> PartialEq for Imposter<A> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.as_imposter_ref() == other.as_imposter_ref()
+    }
+}
+
+impl<A: GlobalAlloc> Hash for Imposter<A> {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_imposter_ref().hash(state)
+    }
+}
+
+impl<A: GlobalAlloc> PartialOrd for Imposter<A> {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.as_imposter_ref().partial_cmp(&other.as_imposter_ref())
+    }
+}
+
+impl<A: GlobalAlloc> Drop for Imposter<A> {
     #[inline]
     fn drop(&mut self) {
         unsafe {
-            let ptr = self.data.as_ptr();
-            if let Some(drop) = self.drop {
-                (drop)(ptr);
-            }
+            let data = self.data_mut();
+            // release the heap memory even if the drop function panics
+            let _guard = self.is_allocated().then(|| DeallocGuard {
+                ptr: data,
+                layout: self.ty.layout(),
+                alloc: &self.alloc,
+            });
 
-            if self.layout.size() != 0 {
-                dealloc(ptr, self.layout);
+            if let Some(drop) = self.ty.drop_fn() {
+                (drop)(data);
             }
         }
     }
@@ -35,20 +107,21 @@ impl Drop for Imposter {
 
 impl<T: 'static> From<Box<T>> for Imposter {
     fn from(item: Box<T>) -> Self {
-        let data = unsafe {
-            let data_ptr = Box::into_raw(item);
-            ptr::NonNull::new_unchecked(data_ptr).cast::<u8>()
-        };
+        unsafe { Self::from_box_unchecked(item, ImposterType::of::<T>()) }
+    }
+}
 
-        Self {
-            data,
-            typeid: TypeId::of::<T>(),
-            layout: Layout::new::<T>(),
-            drop: match mem::needs_drop::<T>() {
-                false => None,
-                true => Some(Self::drop_impl::<T>),
-            },
-        }
+impl<T: 'static> From<Box<[T]>> for Imposter {
+    #[inline]
+    fn from(item: Box<[T]>) -> Self {
+        Self::from_unsized(item)
+    }
+}
+
+impl From<Box<str>> for Imposter {
+    #[inline]
+    fn from(item: Box<str>) -> Self {
+        Self::from_unsized(item)
     }
 }
 
@@ -56,45 +129,122 @@ impl Imposter {
     /// Creates a new imposter containing `item`
     #[inline]
     pub fn new<T: 'static>(item: T) -> Self {
-        Box::new(item).into()
+        unsafe { Self::new_unchecked(item, ImposterType::of::<T>()) }
     }
 
-    pub(crate) unsafe fn from_raw(
-        data: ptr::NonNull<u8>,
-        typeid: TypeId,
-        layout: Layout,
-        drop: Option<ImposterDrop>,
-    ) -> Self {
+    /// Creates a new imposter containing `item` that can be cloned using [`Imposter::try_clone`]
+    #[inline]
+    pub fn new_cloneable<T: Clone + 'static>(item: T) -> Self {
+        unsafe { Self::new_unchecked(item, ImposterType::cloneable::<T>()) }
+    }
+
+    /// Creates a new imposter containing `item` that formats its contents when debugged
+    #[inline]
+    pub fn new_debuggable<T: fmt::Debug + 'static>(item: T) -> Self {
+        unsafe { Self::new_unchecked(item, ImposterType::debuggable::<T>()) }
+    }
+
+    /// Creates a new imposter containing the unsized `item`, like a slice, `str` or trait object
+    ///
+    /// The imposter contains the box itself, so it is described by `Box<U>`.
+    /// A box of an unsized type is two pointers wide and can not be stored inline,
+    /// so the box is moved into a second allocation and the item is reached through two pointers.
+    /// The item can be retrieved using [`Imposter::downcast_unsized`].
+    ///
+    /// # Example
+    /// ```
+    /// use std::fmt::Display;
+    /// use imposters::Imposter;
+    ///
+    /// let imposter = Imposter::from_unsized::<dyn Display>(Box::new(42));
+    /// let display = imposter.downcast_unsized_ref::<dyn Display>().unwrap();
+    /// assert_eq!(display.to_string(), "42");
+    /// ```
+    #[inline]
+    pub fn from_unsized<U: ?Sized + 'static>(item: Box<U>) -> Self {
+        Self::new(item)
+    }
+
+    /// Creates a new imposter containing `item` that is described by `ty`
+    ///
+    /// If `ty` does not describe `T`, the item is returned in `Err`
+    #[inline]
+    pub fn with_type<T: 'static>(item: T, ty: &'static ImposterType) -> Result<Self, Rejected<T>> {
+        if !ty.is::<T>() {
+            return Err(Rejected::new(item, ty.mismatch::<T>()));
+        }
+
+        Ok(unsafe { Self::new_unchecked(item, ty) })
+    }
+
+    /// Creates a new imposter containing `item` that is described by `ty`
+    ///
+    /// # Safety
+    /// `ty` must describe `T`
+    #[inline]
+    pub(crate) unsafe fn new_unchecked<T>(item: T, ty: &'static ImposterType) -> Self {
+        Self::new_unchecked_in(item, ty, Global)
+    }
+
+    /// Creates a new imposter containing the boxed `item` that is described by `ty`
+    ///
+    /// # Safety
+    /// `ty` must describe `T`
+    unsafe fn from_box_unchecked<T>(item: Box<T>, ty: &'static ImposterType) -> Self {
+        if ty.is_inline() {
+            return Self::new_unchecked(*item, ty);
+        }
+
+        let data = ImposterData {
+            heap: ptr::NonNull::from(Box::leak(item)).cast(),
+        };
+
         Self {
             data,
-            typeid,
-            layout,
-            drop,
+            ty,
+            alloc: Global,
         }
     }
 
-    /// Downcasts the data in this imposter to an owned type `T`.
+    /// Creates a new imposter containing the default value of the type described by `ty` as `Some(Imposter)`
     ///
-    /// If `T` does not match the internal type, the imposter is returned in `Err`
+    /// Returns `None` if no default constructor was recorded for the type.
+    /// See [`ImposterTypeBuilder::with_default`](crate::ImposterTypeBuilder::with_default).
     #[inline]
-    pub fn downcast<T: 'static>(self) -> Result<T, Self> {
-        self.downcast_box().map(|b| *b)
+    pub fn default_for(ty: &'static ImposterType) -> Option<Self> {
+        Self::default_for_in(ty, Global)
     }
 
-    /// Downcasts the data in this imposter to an owned type `T`.
+    /// Creates a new imposter described by `ty` by moving the value at `src` into it
+    ///
+    /// This is useful for types that are only known at runtime, see [`ImposterType::runtime`].
     ///
     /// # Safety
-    /// - `T` must match the internal type
-    pub unsafe fn downcast_unchecked<T: 'static>(self) -> T {
-        *self.downcast_box_unchecked()
+    /// - `src` must point to a valid value of the type described by `ty`
+    /// - the value at `src` is moved, so it must not be used or dropped again
+    #[inline]
+    pub unsafe fn from_raw(src: *const u8, ty: &'static ImposterType) -> Self {
+        Self::from_raw_in(src, ty, Global)
+    }
+
+    /// Creates a new imposter described by `ty` whose data is initialized by `init`
+    ///
+    /// See [`Imposter::init_with_in`].
+    ///
+    /// # Safety
+    /// `init` must initialize the memory with a valid value of the type described by `ty`
+    #[inline]
+    pub unsafe fn init_with(ty: &'static ImposterType, init: impl FnOnce(*mut u8)) -> Self {
+        Self::init_with_in(ty, Global, init)
     }
 
     /// Downcasts the data in this imposter to an owned type `Box<T>`.
     ///
     /// If `T` does not match the internal type, the imposter is returned in `Err`
-    pub fn downcast_box<T: 'static>(self) -> Result<Box<T>, Self> {
-        if self.has_type_id::<T>() {
-            return Err(self);
+    pub fn downcast_box<T: 'static>(self) -> Result<Box<T>, Rejected<Self>> {
+        if !self.has_type_id::<T>() {
+            let error = self.ty.mismatch::<T>();
+            return Err(Rejected::new(self, error));
         }
 
         // SAFETY:
@@ -106,18 +256,203 @@ impl Imposter {
     ///
     /// # Safety
     /// - `T` must match the internal type
-    pub unsafe fn downcast_box_unchecked<T: 'static>(self) -> Box<T> {
-        let item = unsafe { Box::from_raw(self.data.as_ptr() as *mut T) };
+    pub unsafe fn downcast_box_unchecked<T>(self) -> Box<T> {
+        if self.is_inline() {
+            return Box::new(self.downcast_unchecked());
+        }
+
+        let item = Box::from_raw(self.data.heap.as_ptr() as *mut T);
         mem::forget(self);
         item
     }
 
+    /// Consumes this imposter and leaks its data, returning `Ok(&'static mut T)`
+    ///
+    /// If `T` does not match the internal type, the imposter is returned in `Err`
+    #[inline]
+    pub fn leak<T: 'static>(self) -> Result<&'static mut T, Rejected<Self>> {
+        self.downcast_box().map(Box::leak)
+    }
+
+    /// Consumes this imposter, returning a pointer to its data and the [`ImposterType`] describing it
+    ///
+    /// See [`Imposter::into_raw_parts_with_alloc`].
+    #[inline]
+    pub fn into_raw_parts(self) -> (ptr::NonNull<u8>, &'static ImposterType) {
+        let (ptr, ty, _) = self.into_raw_parts_with_alloc();
+        (ptr, ty)
+    }
+
+    /// Rebuilds an imposter from a pointer and [`ImposterType`] returned by [`Imposter::into_raw_parts`]
+    ///
+    /// # Safety
+    /// See [`Imposter::from_raw_parts_in`].
+    #[inline]
+    pub unsafe fn from_raw_parts(ptr: ptr::NonNull<u8>, ty: &'static ImposterType) -> Self {
+        Self::from_raw_parts_in(ptr, ty, Global)
+    }
+}
+
+impl<A: GlobalAlloc + Clone> Imposter<A> {
+    /// Returns a clone of this imposter, allocated using the same allocator
+    ///
+    /// Returns `None` if no clone function was recorded for the internal type.
+    /// See [`Imposter::new_cloneable`].
+    #[inline]
+    pub fn try_clone(&self) -> Option<Self> {
+        let clone = self.ty.clone_fn()?;
+        let src = self.data().as_ptr();
+        let alloc = self.alloc.clone();
+        Some(unsafe { Self::init_with_in(self.ty, alloc, |dst| clone(src, dst)) })
+    }
+}
+
+impl<A: GlobalAlloc> Imposter<A> {
+    /// Creates a new imposter containing `item`, allocated using `alloc`
+    #[inline]
+    pub fn new_in<T: 'static>(item: T, alloc: A) -> Self {
+        unsafe { Self::new_unchecked_in(item, ImposterType::of::<T>(), alloc) }
+    }
+
+    /// Creates a new imposter containing `item` that is described by `ty`, allocated using `alloc`
+    ///
+    /// If `ty` does not describe `T`, the item is returned in `Err`
+    #[inline]
+    pub fn with_type_in<T: 'static>(
+        item: T,
+        ty: &'static ImposterType,
+        alloc: A,
+    ) -> Result<Self, Rejected<T>> {
+        if !ty.is::<T>() {
+            return Err(Rejected::new(item, ty.mismatch::<T>()));
+        }
+
+        Ok(unsafe { Self::new_unchecked_in(item, ty, alloc) })
+    }
+
+    /// Creates a new imposter containing `item` that is described by `ty`, allocated using `alloc`
+    ///
+    /// # Safety
+    /// `ty` must describe `T`
+    #[inline]
+    pub(crate) unsafe fn new_unchecked_in<T>(item: T, ty: &'static ImposterType, alloc: A) -> Self {
+        Self::init_with_in(ty, alloc, |dst| (dst as *mut T).write(item))
+    }
+
+    /// Creates a new imposter containing the default value of the type described by `ty`, allocated using `alloc`
+    ///
+    /// Returns `None` if no default constructor was recorded for the type.
+    #[inline]
+    pub fn default_for_in(ty: &'static ImposterType, alloc: A) -> Option<Self> {
+        let default = ty.default_fn()?;
+        Some(unsafe { Self::init_with_in(ty, alloc, |dst| default(dst)) })
+    }
+
+    /// Creates a new imposter described by `ty` by moving the value at `src` into it, allocated using `alloc`
+    ///
+    /// # Safety
+    /// - `src` must point to a valid value of the type described by `ty`
+    /// - the value at `src` is moved, so it must not be used or dropped again
+    #[inline]
+    pub unsafe fn from_raw_in(src: *const u8, ty: &'static ImposterType, alloc: A) -> Self {
+        let size = ty.layout().size();
+        Self::init_with_in(ty, alloc, |dst| ptr::copy_nonoverlapping(src, dst, size))
+    }
+
+    /// Creates a new imposter described by `ty` whose data is initialized by `init`, allocated using `alloc`
+    ///
+    /// `init` is given a pointer to uninitialized memory that fits the type described by `ty`.
+    /// If `init` panics, the memory is released without being dropped.
+    ///
+    /// # Safety
+    /// `init` must initialize the memory with a valid value of the type described by `ty`
+    pub unsafe fn init_with_in(
+        ty: &'static ImposterType,
+        alloc: A,
+        init: impl FnOnce(*mut u8),
+    ) -> Self {
+        match Self::try_init_with_in(ty, alloc, |dst| {
+            init(dst);
+            Ok::<_, Infallible>(())
+        }) {
+            Ok(imposter) => imposter,
+            Err(never) => match never {},
+        }
+    }
+
+    /// Creates a new imposter described by `ty` whose data is initialized by the fallible `init`
+    ///
+    /// If `init` fails or panics, the memory is released without being dropped.
+    ///
+    /// # Safety
+    /// If `init` returns `Ok`, it must have initialized the memory with a valid value of the type described by `ty`
+    pub(crate) unsafe fn try_init_with_in<E>(
+        ty: &'static ImposterType,
+        alloc: A,
+        init: impl FnOnce(*mut u8) -> Result<(), E>,
+    ) -> Result<Self, E> {
+        let mut data = ImposterData::uninit();
+        if ty.is_inline() {
+            init(ptr::addr_of_mut!(data.inline) as *mut u8)?;
+            return Ok(Self { data, ty, alloc });
+        }
+
+        let layout = ty.layout();
+        if layout.size() == 0 {
+            // over aligned zero sized types are not inline, but never need to be allocated
+            data.heap = create_dangling_ptr(&layout);
+            init(data.heap.as_ptr())?;
+            return Ok(Self { data, ty, alloc });
+        }
+
+        let heap_ptr = alloc.alloc(layout);
+        if heap_ptr.is_null() {
+            handle_alloc_error(layout);
+        }
+
+        let guard = DeallocGuard {
+            ptr: heap_ptr,
+            layout,
+            alloc: &alloc,
+        };
+        init(heap_ptr)?;
+        mem::forget(guard);
+
+        data.heap = ptr::NonNull::new_unchecked(heap_ptr);
+        Ok(Self { data, ty, alloc })
+    }
+
+    /// Downcasts the data in this imposter to an owned type `T`.
+    ///
+    /// If `T` does not match the internal type, the imposter is returned in `Err`
+    #[inline]
+    pub fn downcast<T: 'static>(self) -> Result<T, Rejected<Self>> {
+        if !self.has_type_id::<T>() {
+            let error = self.ty.mismatch::<T>();
+            return Err(Rejected::new(self, error));
+        }
+
+        // SAFETY:
+        // raw pointer type is checked before conversion
+        Ok(unsafe { self.downcast_unchecked() })
+    }
+
+    /// Downcasts the data in this imposter to an owned type `T`.
+    ///
+    /// # Safety
+    /// - `T` must match the internal type
+    pub unsafe fn downcast_unchecked<T>(self) -> T {
+        let item = ptr::read(self.data().as_ptr() as *const T);
+        self.dispose_and_forget();
+        item
+    }
+
     /// Downcasts the data in this imposter to type `&T`.
     ///
     /// If `T` does not match the internal type, `None` is returned.
     #[inline]
     pub fn downcast_ref<T: 'static>(&self) -> Option<&T> {
-        if self.has_type_id::<T>() {
+        if !self.has_type_id::<T>() {
             return None;
         }
 
@@ -130,8 +465,8 @@ impl Imposter {
     ///
     /// # Safety
     /// - `T` must match the internal type
-    pub unsafe fn downcast_ref_unchecked<T: 'static>(&self) -> &T {
-        &*(self.data.as_ptr() as *mut T)
+    pub unsafe fn downcast_ref_unchecked<T>(&self) -> &T {
+        &*(self.data().as_ptr() as *const T)
     }
 
     /// Downcasts the data in this imposter to type `&mut T`.
@@ -139,7 +474,7 @@ impl Imposter {
     /// If `T` does not match the internal type, `None` is returned.
     #[inline]
     pub fn downcast_mut<T: 'static>(&mut self) -> Option<&mut T> {
-        if self.has_type_id::<T>() {
+        if !self.has_type_id::<T>() {
             return None;
         }
 
@@ -148,70 +483,392 @@ impl Imposter {
         Some(unsafe { self.downcast_mut_unchecked() })
     }
 
+    /// Downcasts the unsized data in this imposter to an owned type `Box<U>`.
+    ///
+    /// If the imposter was not created from a `Box<U>`, the imposter is returned in `Err`.
+    /// See [`Imposter::from_unsized`].
+    #[inline]
+    pub fn downcast_unsized<U: ?Sized + 'static>(self) -> Result<Box<U>, Rejected<Self>> {
+        self.downcast::<Box<U>>()
+    }
+
+    /// Downcasts the unsized data in this imposter to type `&U`.
+    ///
+    /// If the imposter was not created from a `Box<U>`, `None` is returned.
+    #[inline]
+    pub fn downcast_unsized_ref<U: ?Sized + 'static>(&self) -> Option<&U> {
+        self.downcast_ref::<Box<U>>().map(|item| &**item)
+    }
+
+    /// Downcasts the unsized data in this imposter to type `&mut U`.
+    ///
+    /// If the imposter was not created from a `Box<U>`, `None` is returned.
+    #[inline]
+    pub fn downcast_unsized_mut<U: ?Sized + 'static>(&mut self) -> Option<&mut U> {
+        self.downcast_mut::<Box<U>>().map(|item| &mut **item)
+    }
+
+    /// Views the data in this imposter as the trait object `&Dyn`
+    ///
+    /// If no [`DynCaster`](crate::DynCaster) for `Dyn` was registered with the internal type, `None` is returned.
+    /// See [`ImposterTypeBuilder::with_dyn`](crate::ImposterTypeBuilder::with_dyn).
+    #[inline]
+    pub fn as_dyn<Dyn: ?Sized + 'static>(&self) -> Option<&Dyn> {
+        let caster = self.ty.dyn_caster::<Dyn>()?;
+        Some(unsafe { caster.cast_ref(self.data().as_ptr()) })
+    }
+
+    /// Views the data in this imposter as the trait object `&mut Dyn`
+    ///
+    /// If no [`DynCaster`](crate::DynCaster) for `Dyn` was registered with the internal type, `None` is returned.
+    #[inline]
+    pub fn as_dyn_mut<Dyn: ?Sized + 'static>(&mut self) -> Option<&mut Dyn> {
+        let caster = self.ty.dyn_caster::<Dyn>()?;
+        Some(unsafe { caster.cast_mut(self.data_mut()) })
+    }
+
     /// Downcasts the data in this imposter to type `&mut T`.
     ///
     /// # Safety
     /// - `T` must match the internal type
-    pub unsafe fn downcast_mut_unchecked<T: 'static>(&mut self) -> &mut T {
-        &mut *(self.data.as_ptr() as *mut T)
+    pub unsafe fn downcast_mut_unchecked<T>(&mut self) -> &mut T {
+        &mut *(self.data_mut() as *mut T)
+    }
+
+    /// Replaces the data in this imposter with `value`, returning the old value as `Ok(T)`
+    ///
+    /// If `T` does not match the internal type, `value` is given back in `Err`
+    #[inline]
+    pub fn replace<T: 'static>(&mut self, value: T) -> Result<T, T> {
+        match self.downcast_mut::<T>() {
+            Some(item) => Ok(mem::replace(item, value)),
+            None => Err(value),
+        }
+    }
+
+    /// Takes the data out of this imposter as `Some(T)`, leaving [`T::default()`](Default::default) in its place
+    ///
+    /// If `T` does not match the internal type, `None` is returned.
+    #[inline]
+    pub fn take<T: Default + 'static>(&mut self) -> Option<T> {
+        self.downcast_mut::<T>().map(mem::take)
+    }
+
+    /// Converts the data in this imposter from type `T` into type `U` using `f`
+    ///
+    /// If `T` and `U` share the same layout, the existing allocation is reused.
+    /// The returned imposter is described by [`ImposterType::of::<U>`](ImposterType::of).
+    ///
+    /// If `T` does not match the internal type, the imposter is returned in `Err`
+    pub fn map<T: 'static, U: 'static>(
+        self,
+        f: impl FnOnce(T) -> U,
+    ) -> Result<Imposter<A>, Rejected<Self>> {
+        if !self.has_type_id::<T>() {
+            let error = self.ty.mismatch::<T>();
+            return Err(Rejected::new(self, error));
+        }
+
+        let ty = ImposterType::of::<U>();
+        let imposter = ManuallyDrop::new(self);
+        // SAFETY:
+        // the type is checked above, and the imposter is never dropped
+        let (item, alloc) = unsafe {
+            let item = ptr::read(imposter.data().as_ptr() as *const T);
+            if imposter.layout() != ty.layout() {
+                imposter.dealloc();
+            }
+            (item, ptr::read(&imposter.alloc))
+        };
+
+        if imposter.layout() != ty.layout() {
+            return Ok(unsafe { Imposter::new_unchecked_in(f(item), ty, alloc) });
+        }
+
+        let guard = (!imposter.is_inline()).then(|| DeallocGuard {
+            ptr: unsafe { imposter.data.heap.as_ptr() },
+            layout: ty.layout(),
+            alloc: &alloc,
+        });
+        let value = f(item);
+        mem::forget(guard);
+
+        let mut mapped = Imposter {
+            data: imposter.data,
+            ty,
+            alloc,
+        };
+        unsafe { (mapped.data_mut() as *mut U).write(value) };
+        Ok(mapped)
+    }
+
+    /// Converts this imposter into a [`PinnedImposter`] whose data will never move again
+    ///
+    /// Values that are stored inline are first moved into a new allocation.
+    #[inline]
+    pub fn into_pin(self) -> PinnedImposter<A> {
+        self.into()
+    }
+
+    /// Consumes this imposter, returning a pointer to its data, the [`ImposterType`] describing it and its allocator
+    ///
+    /// The data always lives in memory allocated by the returned allocator,
+    /// so values that are stored inline are first moved into a new allocation.
+    /// The caller becomes responsible for the data,
+    /// which can be rebuilt into an imposter using [`Imposter::from_raw_parts_in`].
+    pub fn into_raw_parts_with_alloc(self) -> (ptr::NonNull<u8>, &'static ImposterType, A) {
+        let imposter = ManuallyDrop::new(self);
+        let ty = imposter.ty;
+        // SAFETY:
+        // the imposter is never dropped, so the allocator is only read once
+        let alloc = unsafe { ptr::read(&imposter.alloc) };
+        if !imposter.is_inline() {
+            return (unsafe { imposter.data.heap }, ty, alloc);
+        }
+
+        let layout = ty.layout();
+        if layout.size() == 0 {
+            return (create_dangling_ptr(&layout), ty, alloc);
+        }
+
+        unsafe {
+            let heap_ptr = alloc.alloc(layout);
+            if heap_ptr.is_null() {
+                handle_alloc_error(layout);
+            }
+
+            ptr::copy_nonoverlapping(imposter.data().as_ptr(), heap_ptr, layout.size());
+            (ptr::NonNull::new_unchecked(heap_ptr), ty, alloc)
+        }
+    }
+
+    /// Rebuilds an imposter from the parts returned by [`Imposter::into_raw_parts_with_alloc`]
+    ///
+    /// # Safety
+    /// - `ptr` must point to a valid value of the type described by `ty`
+    /// - `ptr` must have been allocated by `alloc` using the layout of `ty`,
+    ///   or be dangling if that layout has a size of zero
+    /// - ownership of the value and its memory is transferred to the returned imposter
+    pub unsafe fn from_raw_parts_in(
+        ptr: ptr::NonNull<u8>,
+        ty: &'static ImposterType,
+        alloc: A,
+    ) -> Self {
+        if !ty.is_inline() {
+            let data = ImposterData { heap: ptr };
+            return Self { data, ty, alloc };
+        }
+
+        let layout = ty.layout();
+        let mut data = ImposterData::uninit();
+        ptr::copy_nonoverlapping(
+            ptr.as_ptr(),
+            ptr::addr_of_mut!(data.inline) as *mut u8,
+            layout.size(),
+        );
+        if layout.size() != 0 {
+            alloc.dealloc(ptr.as_ptr(), layout);
+        }
+
+        Self { data, ty, alloc }
     }
 
     /// Disposes of this imposter and deallocates the data it points to ***without*** calling its destructor
     pub fn dispose_and_forget(self) {
-        unsafe { dealloc(self.data.as_ptr(), self.layout) };
-        mem::forget(self);
+        let mut imposter = ManuallyDrop::new(self);
+        unsafe {
+            imposter.dealloc();
+            ptr::drop_in_place(&mut imposter.alloc);
+        }
     }
 
-    /// Returns a reference to the internal data pointer
+    /// Deallocates the data this imposter points to
+    ///
+    /// # Safety
+    /// The data must not be used again after calling this
+    #[inline]
+    unsafe fn dealloc(&self) {
+        if self.is_allocated() {
+            self.alloc
+                .dealloc(self.data.heap.as_ptr(), self.ty.layout());
+        }
+    }
+
+    /// Returns true if the internal data lives in memory owned by the allocator
+    ///
+    /// Zero sized data is never allocated, even when it is not stored inline.
+    #[inline]
+    fn is_allocated(&self) -> bool {
+        !self.is_inline() && self.ty.layout().size() != 0
+    }
+
+    /// Returns a reference to the allocator used by this imposter
+    #[inline]
+    pub fn allocator(&self) -> &A {
+        &self.alloc
+    }
+
+    /// Returns a pointer to the internal data
+    ///
+    /// If the data is stored inline, this points into the imposter itself
+    /// and is invalidated when the imposter is moved.
+    /// The data must not be mutated through this pointer.
     #[inline]
     pub fn data(&self) -> ptr::NonNull<u8> {
-        self.data
+        unsafe {
+            match self.is_inline() {
+                false => self.data.heap,
+                true => ptr::NonNull::new_unchecked(ptr::addr_of!(self.data.inline) as *mut u8),
+            }
+        }
+    }
+
+    /// Returns a mutable pointer to the internal data
+    #[inline]
+    fn data_mut(&mut self) -> *mut u8 {
+        unsafe {
+            match self.is_inline() {
+                false => self.data.heap.as_ptr(),
+                true => ptr::addr_of_mut!(self.data.inline) as *mut u8,
+            }
+        }
+    }
+
+    /// Wraps this imposter as an [`ImposterKey`], returning `Ok(ImposterKey)`
+    ///
+    /// If no hash function was recorded for the internal type, the imposter is returned in `Err`
+    #[inline]
+    pub fn into_key(self) -> Result<ImposterKey<Self>, Self> {
+        match self.ty.hash_fn() {
+            Some(_) => Ok(ImposterKey::new_unchecked(self)),
+            None => Err(self),
+        }
+    }
+
+    /// Returns a borrowed [`ImposterRef`] to the internal data
+    #[inline]
+    pub fn as_imposter_ref(&self) -> ImposterRef<'_> {
+        unsafe { ImposterRef::from_raw(self.data(), self.ty) }
+    }
+
+    /// Returns a mutably borrowed [`ImposterMut`] to the internal data
+    #[inline]
+    pub fn as_imposter_mut(&mut self) -> ImposterMut<'_> {
+        let ptr = self.data_mut();
+        unsafe { ImposterMut::from_raw(ptr::NonNull::new_unchecked(ptr), self.ty) }
+    }
+
+    /// Returns a borrowed [`ImposterRef`] to the field found at `path`, like `position.x`
+    ///
+    /// If the path does not lead to a registered field, `None` is returned.
+    /// See [`ImposterType::field_path`].
+    #[inline]
+    pub fn field(&self, path: &str) -> Option<ImposterRef<'_>> {
+        self.as_imposter_ref().field(path)
+    }
+
+    /// Returns a mutably borrowed [`ImposterMut`] to the field found at `path`, like `position.x`
+    ///
+    /// If the path does not lead to a registered field, `None` is returned.
+    /// See [`ImposterType::field_path`].
+    #[inline]
+    pub fn field_mut(&mut self, path: &str) -> Option<ImposterMut<'_>> {
+        self.as_imposter_mut().into_field(path).ok()
+    }
+
+    /// Returns an [`ErasedDebug`] that formats the internal data
+    #[inline]
+    pub fn debug_value(&self) -> ErasedDebug<'_> {
+        unsafe { ErasedDebug::new(self.data().as_ptr(), self.ty) }
+    }
+
+    /// Returns true if the internal data is stored inline instead of on the heap
+    #[inline]
+    pub fn is_inline(&self) -> bool {
+        self.ty.is_inline()
+    }
+
+    /// Returns the [`ImposterType`] describing the internal data
+    #[inline]
+    pub fn imposter_type(&self) -> &'static ImposterType {
+        self.ty
     }
 
     /// Returns a reference to the internal type id
     #[inline]
-    pub fn type_id(&self) -> TypeId {
-        self.typeid
+    pub fn type_id(&self) -> ImposterTypeId {
+        self.ty.type_id()
+    }
+
+    /// Returns the name of the internal type
+    #[inline]
+    pub fn type_name(&self) -> &'static str {
+        self.ty.type_name()
     }
 
     /// Returns true if `T` matches the internal type
+    #[inline]
     pub fn has_type_id<T: 'static>(&self) -> bool {
-        self.typeid != TypeId::of::<T>()
+        self.ty.is::<T>()
     }
 
     /// Returns a reference to the internal layout
     #[inline]
     pub fn layout(&self) -> Layout {
-        self.layout
+        self.ty.layout()
     }
 
     /// Returns a reference to the internal drop function
     #[inline]
     pub fn drop_fn(&self) -> Option<ImposterDrop> {
-        self.drop
-    }
-
-    /// This is the function used if data needs to be dropped inside a imposter
-    #[inline]
-    pub(crate) unsafe fn drop_impl<T>(ptr: *mut u8) {
-        ptr::drop_in_place(ptr as *mut T);
+        self.ty.drop_fn()
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::{any::Any, rc::Rc};
+
     use droptest::{assert_drop, DropRegistry};
 
     use super::*;
 
+    #[derive(Debug)]
     struct Test1(u32);
-    struct Test2(u64);
+    #[derive(Debug)]
+    struct Test2;
 
     #[test]
     fn new_imposter() {
         let test1 = Test1(42);
         let imposter = Imposter::new(test1);
-        assert!(unsafe { &*(imposter.data.as_ptr() as *mut Test1) }.0 == 42);
+        assert!(unsafe { &*(imposter.data().as_ptr() as *mut Test1) }.0 == 42);
+    }
+
+    #[test]
+    fn inline_imposter() {
+        let mut imposter = Imposter::new(42u32);
+        assert!(imposter.is_inline());
+        *imposter.downcast_mut::<u32>().unwrap() += 1;
+        assert_eq!(*imposter.downcast_box::<u32>().unwrap(), 43);
+
+        let imposter = Imposter::from(Box::new(()));
+        assert!(imposter.is_inline());
+        imposter.downcast::<()>().unwrap();
+
+        let imposter = Imposter::new([1u64, 2, 3]);
+        assert!(!imposter.is_inline());
+        assert_eq!(imposter.downcast::<[u64; 3]>().unwrap(), [1, 2, 3]);
+    }
+
+    #[test]
+    fn drop_inline_imposter() {
+        let rc = Rc::new(42);
+        let imposter = Imposter::new(rc.clone());
+        assert!(imposter.is_inline());
+        assert_eq!(Rc::strong_count(&rc), 2);
+        drop(imposter);
+        assert_eq!(Rc::strong_count(&rc), 1);
     }
 
     #[test]
@@ -220,10 +877,96 @@ mod tests {
         let imposter = Imposter::new(test1);
         assert!(imposter.downcast_ref::<Test1>().is_some());
         assert!(imposter.downcast_ref::<Test2>().is_none());
+        let imposter = imposter.downcast::<Test2>().unwrap_err().into_value();
         let test1 = imposter.downcast::<Test1>().unwrap();
         assert!(test1.0 == 42);
     }
 
+    #[test]
+    fn imposter_downcast_error() {
+        let imposter = Imposter::new(Test1(42));
+        let error = imposter.downcast::<Test2>().unwrap_err();
+        assert_eq!(error.error().expected(), std::any::type_name::<Test1>());
+        assert_eq!(error.error().found(), std::any::type_name::<Test2>());
+        assert!(error.into_value().has_type_id::<Test1>());
+    }
+
+    #[test]
+    fn imposter_type_name() {
+        let imposter = Imposter::new(Test1(42));
+        assert_eq!(imposter.type_name(), std::any::type_name::<Test1>());
+        assert!(format!("{imposter:?}").contains(std::any::type_name::<Test1>()));
+    }
+
+    #[test]
+    fn clone_imposter() {
+        assert!(Imposter::new(String::from("sus")).try_clone().is_none());
+
+        let imposter = Imposter::new_cloneable(String::from("sus"));
+        let clone = imposter.try_clone().unwrap();
+        assert_eq!(imposter.downcast::<String>().unwrap(), "sus");
+        assert_eq!(clone.downcast::<String>().unwrap(), "sus");
+
+        let imposter = Imposter::new_cloneable(42u32);
+        assert_eq!(imposter.try_clone().unwrap().downcast::<u32>().unwrap(), 42);
+    }
+
+    #[test]
+    fn imposter_with_type() {
+        static TYPE: ImposterType = ImposterType::builder::<u64>().with_clone().build();
+        let imposter = Imposter::with_type(42u64, &TYPE).unwrap();
+        assert!(imposter.try_clone().is_some());
+        assert_eq!(
+            Imposter::with_type(42u32, &TYPE).unwrap_err().into_value(),
+            42
+        );
+    }
+
+    #[test]
+    fn debug_imposter() {
+        let imposter = Imposter::new_debuggable(String::from("sus"));
+        assert_eq!(format!("{imposter:?}"), "\"sus\"");
+        assert_eq!(format!("{:?}", imposter.debug_value()), "\"sus\"");
+    }
+
+    #[test]
+    fn unsized_imposter() {
+        let mut imposter = Imposter::from(Box::<[u32]>::from([1, 2, 3]));
+        imposter.downcast_unsized_mut::<[u32]>().unwrap()[0] = 0;
+        assert!(imposter.downcast_unsized_ref::<str>().is_none());
+        assert_eq!(*imposter.downcast_unsized::<[u32]>().unwrap(), [0, 2, 3]);
+
+        let imposter = Imposter::from(Box::<str>::from("sus"));
+        assert_eq!(imposter.downcast_unsized_ref::<str>().unwrap(), "sus");
+
+        let imposter = Imposter::from_unsized::<dyn Any>(Box::new(Test1(42)));
+        let any = imposter.downcast_unsized::<dyn Any>().unwrap();
+        assert_eq!(any.downcast_ref::<Test1>().unwrap().0, 42);
+    }
+
+    #[test]
+    fn dyn_imposter() {
+        trait Sus {
+            fn sus(&mut self) -> u32;
+        }
+
+        impl Sus for Test1 {
+            fn sus(&mut self) -> u32 {
+                self.0 += 1;
+                self.0
+            }
+        }
+
+        static TYPE: ImposterType = ImposterType::builder::<Test1>()
+            .with_dyn(&[crate::dyn_cast!(Test1 as dyn Sus)])
+            .build();
+
+        let mut imposter = Imposter::with_type(Test1(42), &TYPE).unwrap();
+        assert_eq!(imposter.as_dyn_mut::<dyn Sus>().unwrap().sus(), 43);
+        assert!(imposter.as_dyn::<dyn fmt::Display>().is_none());
+        assert!(Imposter::new(Test1(42)).as_dyn::<dyn Sus>().is_none());
+    }
+
     #[test]
     fn drop_imposter() {
         let test1 = Test1(42);
@@ -236,4 +979,194 @@ mod tests {
         drop(guard);
         assert_drop!(registry, guard_id);
     }
+
+    #[test]
+    fn default_imposter() {
+        static TYPE: ImposterType = ImposterType::builder::<Vec<u32>>().with_default().build();
+        assert!(Imposter::default_for(ImposterType::of::<Vec<u32>>()).is_none());
+        let imposter = Imposter::default_for(&TYPE).unwrap();
+        assert!(imposter.downcast::<Vec<u32>>().unwrap().is_empty());
+    }
+
+    #[test]
+    fn replace_imposter() {
+        let mut imposter = Imposter::new(String::from("red"));
+        assert_eq!(imposter.replace(String::from("sus")).unwrap(), "red");
+        assert_eq!(imposter.replace(42u32).unwrap_err(), 42);
+        assert_eq!(imposter.take::<u32>(), None);
+        assert_eq!(imposter.take::<String>().unwrap(), "sus");
+        assert_eq!(imposter.downcast::<String>().unwrap(), "");
+    }
+
+    #[test]
+    fn map_imposter() {
+        let imposter = Imposter::new([1u64, 2, 3]);
+        let data = imposter.data();
+        let imposter = imposter
+            .map(|items: [u64; 3]| items.map(|i| -(i as i64)))
+            .unwrap();
+        assert_eq!(imposter.data(), data);
+        assert_eq!(imposter.downcast_ref::<[i64; 3]>().unwrap(), &[-1, -2, -3]);
+
+        let imposter = imposter.map(|items: [i64; 3]| items.to_vec()).unwrap();
+        assert_eq!(imposter.downcast_ref::<Vec<i64>>().unwrap(), &[-1, -2, -3]);
+
+        let imposter = Imposter::new(42u32).map(|i: u32| i as f32).unwrap();
+        assert!(imposter.is_inline());
+        let imposter = imposter.map(|_: u8| ()).unwrap_err().into_value();
+        assert_eq!(imposter.downcast::<f32>().unwrap(), 42.);
+    }
+
+    #[test]
+    fn map_imposter_panic() {
+        let rc = Rc::new(42);
+        let imposter = Imposter::new([rc.clone(), rc.clone()]);
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            imposter.map(|items: [Rc<i32>; 2]| -> [usize; 2] {
+                drop(items);
+                panic!("sus")
+            })
+        }));
+        assert!(result.is_err());
+        assert_eq!(Rc::strong_count(&rc), 1);
+    }
+
+    #[test]
+    fn raw_parts_imposter() {
+        let imposter = Imposter::new(String::from("sus"));
+        let data = imposter.data();
+        let (ptr, ty) = imposter.into_raw_parts();
+        assert_eq!(ptr, data);
+        let imposter = unsafe { Imposter::from_raw_parts(ptr, ty) };
+        assert_eq!(imposter.downcast::<String>().unwrap(), "sus");
+
+        let rc = Rc::new(42);
+        let (ptr, ty) = Imposter::new(rc.clone()).into_raw_parts();
+        assert_eq!(unsafe { &*(ptr.as_ptr() as *const Rc<i32>) }, &rc);
+        let imposter = unsafe { Imposter::from_raw_parts(ptr, ty) };
+        assert!(imposter.is_inline());
+        drop(imposter);
+        assert_eq!(Rc::strong_count(&rc), 1);
+
+        let (ptr, ty) = Imposter::new(()).into_raw_parts();
+        unsafe { Imposter::from_raw_parts(ptr, ty) }
+            .downcast::<()>()
+            .unwrap();
+    }
+
+    #[test]
+    fn leak_imposter() {
+        let imposter = Imposter::new(42u32);
+        let imposter = imposter.leak::<u64>().unwrap_err().into_value();
+        let leaked: &'static mut u32 = imposter.leak().unwrap();
+        *leaked += 1;
+        assert_eq!(*leaked, 43);
+        drop(unsafe { Box::from_raw(leaked) });
+    }
+
+    #[test]
+    fn compare_imposter() {
+        static TYPE: ImposterType = ImposterType::builder::<u32>()
+            .with_hash()
+            .with_ord()
+            .build();
+
+        let imposters = [3u32, 1, 3, 2].map(|value| Imposter::with_type(value, &TYPE).unwrap());
+        let unique: std::collections::HashSet<_> = imposters
+            .iter()
+            .map(|imposter| imposter.as_imposter_ref().into_key().unwrap())
+            .collect();
+        assert_eq!(unique.len(), 3);
+
+        let mut sorted: Vec<_> = imposters.iter().collect();
+        sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        let sorted: Vec<u32> = sorted.iter().map(|i| *i.downcast_ref().unwrap()).collect();
+        assert_eq!(sorted, [1, 2, 3, 3]);
+
+        // values without recorded functions are never equal, even zero sized ones
+        let (a, b) = (Imposter::new(1u32), Imposter::new(1u32));
+        assert_ne!(a, b);
+        assert!(a.partial_cmp(&b).is_none());
+        assert_ne!(Imposter::new(()), Imposter::new(()));
+        assert!(a.into_key().is_err());
+
+        let key = Imposter::with_type(3u32, &TYPE)
+            .unwrap()
+            .into_key()
+            .unwrap();
+        assert_eq!(key, key);
+        assert_ne!(
+            Imposter::new(1u64),
+            Imposter::with_type(1u32, &TYPE).unwrap()
+        );
+    }
+
+    #[test]
+    fn compare_symmetric() {
+        static EQ: ImposterType = ImposterType::builder::<u32>().with_eq().build();
+        static HASH: ImposterType = ImposterType::builder::<u32>().with_hash().build();
+
+        let plain = Imposter::new(1u32);
+        let eq = Imposter::with_type(1u32, &EQ).unwrap();
+        let hash = Imposter::with_type(1u32, &HASH).unwrap();
+        for (a, b) in [(&plain, &eq), (&eq, &hash), (&plain, &hash)] {
+            assert_eq!(a == b, b == a);
+            assert_eq!(a.partial_cmp(b), b.partial_cmp(a));
+        }
+
+        assert_eq!(eq, Imposter::with_type(1u32, &EQ).unwrap());
+        assert_ne!(eq, plain);
+        assert_ne!(eq, hash);
+
+        // ordering agrees with equality when only one side records hashing
+        static ORD: ImposterType = ImposterType::builder::<u32>().with_ord().build();
+        static ORD_HASH: ImposterType = ImposterType::builder::<u32>()
+            .with_ord()
+            .with_hash()
+            .build();
+        let ord = Imposter::with_type(1u32, &ORD).unwrap();
+        let ord_hash = Imposter::with_type(1u32, &ORD_HASH).unwrap();
+        assert_ne!(ord, ord_hash);
+        assert!(ord.partial_cmp(&ord_hash).is_none());
+        assert!(ord_hash.partial_cmp(&ord).is_none());
+
+        static FLOAT: ImposterType = ImposterType::builder::<f32>().with_eq().build();
+        let nan = Imposter::with_type(f32::NAN, &FLOAT).unwrap();
+        assert_ne!(nan, nan);
+    }
+
+    #[test]
+    fn runtime_imposter() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        static DROPS: AtomicUsize = AtomicUsize::new(0);
+        unsafe fn drop_counter(ptr: *mut u8) {
+            DROPS.fetch_add(*(ptr as *const u64) as usize, Ordering::Relaxed);
+        }
+
+        static COUNTER: ImposterType = unsafe {
+            ImposterType::runtime(7, "Counter", Layout::new::<[u64; 2]>(), Some(drop_counter))
+        };
+
+        let value = [3u64, 0];
+        let imposter = unsafe { Imposter::from_raw(value.as_ptr() as *const u8, &COUNTER) };
+        assert_eq!(imposter.type_name(), "Counter");
+        let imposter = imposter.downcast::<[u64; 2]>().unwrap_err().into_value();
+        assert_eq!(DROPS.load(Ordering::Relaxed), 0);
+        drop(imposter);
+        assert_eq!(DROPS.load(Ordering::Relaxed), 3);
+    }
+
+    #[test]
+    fn imposter_size() {
+        const WORDS: usize = 2 * mem::size_of::<usize>();
+        assert_eq!(mem::size_of::<Imposter>(), WORDS);
+        assert_eq!(mem::size_of::<Option<Imposter>>(), WORDS);
+        assert_eq!(mem::size_of::<crate::SendImposter>(), WORDS);
+        assert_eq!(mem::size_of::<Option<crate::SyncImposter>>(), WORDS);
+        assert_eq!(mem::size_of::<Option<crate::ScopedImposter>>(), WORDS);
+        assert_eq!(mem::size_of::<Option<PinnedImposter>>(), WORDS);
+        assert_eq!(mem::size_of::<Option<ImposterRef>>(), WORDS);
+        assert_eq!(mem::size_of::<Option<ImposterMut>>(), WORDS);
+    }
 }
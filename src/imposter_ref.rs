@@ -0,0 +1,357 @@
+use std::{
+    alloc::Layout,
+    cmp::Ordering,
+    fmt,
+    hash::{Hash, Hasher},
+    marker::PhantomData,
+    ptr,
+};
+
+use crate::{ErasedDebug, Imposter, ImposterKey, ImposterType, ImposterTypeId};
+
+/// A borrowed reference to type erased data, like a single item in an [`ImposterVec`](crate::collections::vec::ImposterVec)
+///
+/// References are compared, hashed and ordered using the functions recorded in their [`ImposterType`],
+/// see [`ImposterTypeBuilder::with_hash`](crate::ImposterTypeBuilder::with_hash).
+/// References to different types are never equal, and are ordered by their [`ImposterTypeId`].
+/// Values are only compared if both descriptors recorded the same kinds of functions,
+/// otherwise they are never equal and can not be ordered.
+/// Use an [`ImposterKey`](crate::ImposterKey) to store references in hash maps and sets.
+#[derive(Clone, Copy)]
+pub struct ImposterRef<'a> {
+    ptr: ptr::NonNull<u8>,
+    ty: &'static ImposterType,
+    _data: PhantomData<&'a ()>,
+}
+
+impl fmt::Debug for ImposterRef<'_> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.debug_value().fmt(f)
+    }
+}
+
+impl PartialEq for ImposterRef<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        match self.ty.eq_fn() {
+            Some(eq) if self.is_comparable_with(*other) => unsafe {
+                eq(self.as_ptr(), other.as_ptr())
+            },
+            _ => false,
+        }
+    }
+}
+
+impl Hash for ImposterRef<'_> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.type_id().hash(state);
+        if let Some(hash) = self.ty.hash_fn() {
+            unsafe { hash(self.as_ptr(), state) };
+        }
+    }
+}
+
+impl PartialOrd for ImposterRef<'_> {
+    /// Returns `None` if both references have the same type but can not be compared
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        match self.type_id().cmp(&other.type_id()) {
+            Ordering::Equal => (),
+            ordering => return Some(ordering),
+        }
+
+        if !self.is_comparable_with(*other) {
+            return None;
+        }
+
+        match self.ty.cmp_fn() {
+            Some(cmp) => Some(unsafe { cmp(self.as_ptr(), other.as_ptr()) }),
+            None => (self == other).then_some(Ordering::Equal),
+        }
+    }
+}
+
+impl<'a> ImposterRef<'a> {
+    /// Creates a new reference to the data at `ptr` described by `ty`
+    ///
+    /// # Safety
+    /// `ptr` must point to a valid value described by `ty` that is borrowed for `'a`
+    #[inline]
+    pub unsafe fn from_raw(ptr: ptr::NonNull<u8>, ty: &'static ImposterType) -> Self {
+        Self {
+            ptr,
+            ty,
+            _data: PhantomData,
+        }
+    }
+
+    /// Downcasts the referenced data to type `&T`.
+    ///
+    /// If `T` does not match the internal type, `None` is returned.
+    #[inline]
+    pub fn downcast_ref<T: 'static>(self) -> Option<&'a T> {
+        if !self.has_type_id::<T>() {
+            return None;
+        }
+
+        // SAFETY:
+        // raw pointer type is checked before conversion
+        Some(unsafe { &*(self.ptr.as_ptr() as *const T) })
+    }
+
+    /// Views the referenced data as the trait object `&Dyn`
+    ///
+    /// If no [`DynCaster`](crate::DynCaster) for `Dyn` was registered with the internal type, `None` is returned.
+    #[inline]
+    pub fn as_dyn<Dyn: ?Sized + 'static>(self) -> Option<&'a Dyn> {
+        let caster = self.ty.dyn_caster::<Dyn>()?;
+        Some(unsafe { caster.cast_ref(self.ptr.as_ptr()) })
+    }
+
+    /// Returns a reference to the field found at `path`, like `position.x`
+    ///
+    /// If the path does not lead to a registered field, `None` is returned.
+    /// See [`ImposterType::field_path`].
+    #[inline]
+    pub fn field(self, path: &str) -> Option<ImposterRef<'a>> {
+        let field = self.ty.field_path(path)?;
+        let ty = field.imposter_type()?;
+        // SAFETY:
+        // registered fields are always inside the referenced data
+        unsafe {
+            let ptr = ptr::NonNull::new_unchecked(self.ptr.as_ptr().add(field.offset()));
+            Some(ImposterRef::from_raw(ptr, ty))
+        }
+    }
+
+    /// Returns an [`ErasedDebug`] that formats the referenced data
+    #[inline]
+    pub fn debug_value(self) -> ErasedDebug<'a> {
+        unsafe { ErasedDebug::new(self.ptr.as_ptr(), self.ty) }
+    }
+
+    /// Returns a new [`Imposter`] containing a clone of the referenced data
+    ///
+    /// Returns `None` if no clone function was recorded for the internal type.
+    #[inline]
+    pub fn try_clone(self) -> Option<Imposter> {
+        let clone = self.ty.clone_fn()?;
+        let src = self.ptr.as_ptr();
+        Some(unsafe { Imposter::init_with(self.ty, |dst| clone(src, dst)) })
+    }
+
+    /// Returns a pointer to the referenced data
+    #[inline]
+    pub fn as_ptr(self) -> *const u8 {
+        self.ptr.as_ptr()
+    }
+
+    /// Returns the [`ImposterType`] describing the referenced data
+    #[inline]
+    pub fn imposter_type(self) -> &'static ImposterType {
+        self.ty
+    }
+
+    /// Wraps this reference as an [`ImposterKey`], returning `Ok(ImposterKey)`
+    ///
+    /// If no hash function was recorded for the referenced type, this reference is returned in `Err`
+    #[inline]
+    pub fn into_key(self) -> Result<ImposterKey<Self>, Self> {
+        match self.ty.hash_fn() {
+            Some(_) => Ok(ImposterKey::new_unchecked(self)),
+            None => Err(self),
+        }
+    }
+
+    /// Returns true if the values of both references can be compared using the functions of this reference
+    ///
+    /// Descriptors with the same type id describe the same type, so their functions behave the same.
+    /// They still have to record the same kinds of functions, so that equality, hashing and ordering agree.
+    #[inline]
+    fn is_comparable_with(self, other: Self) -> bool {
+        ptr::eq(self.ty, other.ty)
+            || self.type_id() == other.type_id()
+                && self.ty.eq_fn().is_some() == other.ty.eq_fn().is_some()
+                && self.ty.hash_fn().is_some() == other.ty.hash_fn().is_some()
+                && self.ty.cmp_fn().is_some() == other.ty.cmp_fn().is_some()
+    }
+
+    /// Returns the type id of the referenced data
+    #[inline]
+    pub fn type_id(self) -> ImposterTypeId {
+        self.ty.type_id()
+    }
+
+    /// Returns the name of the referenced type
+    #[inline]
+    pub fn type_name(self) -> &'static str {
+        self.ty.type_name()
+    }
+
+    /// Returns true if `T` matches the referenced type
+    #[inline]
+    pub fn has_type_id<T: 'static>(self) -> bool {
+        self.ty.is::<T>()
+    }
+
+    /// Returns the layout of the referenced type
+    #[inline]
+    pub fn layout(self) -> Layout {
+        self.ty.layout()
+    }
+}
+
+/// A mutably borrowed reference to type erased data, like a single item in an [`ImposterVec`](crate::collections::vec::ImposterVec)
+pub struct ImposterMut<'a> {
+    ptr: ptr::NonNull<u8>,
+    ty: &'static ImposterType,
+    _data: PhantomData<&'a mut ()>,
+}
+
+impl fmt::Debug for ImposterMut<'_> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.as_ref().fmt(f)
+    }
+}
+
+impl<'a> ImposterMut<'a> {
+    /// Creates a new mutable reference to the data at `ptr` described by `ty`
+    ///
+    /// # Safety
+    /// `ptr` must point to a valid value described by `ty` that is mutably borrowed for `'a`
+    #[inline]
+    pub unsafe fn from_raw(ptr: ptr::NonNull<u8>, ty: &'static ImposterType) -> Self {
+        Self {
+            ptr,
+            ty,
+            _data: PhantomData,
+        }
+    }
+
+    /// Returns an immutable [`ImposterRef`] to the referenced data
+    #[inline]
+    pub fn as_ref(&self) -> ImposterRef<'_> {
+        unsafe { ImposterRef::from_raw(self.ptr, self.ty) }
+    }
+
+    /// Downcasts the referenced data to type `&T`.
+    ///
+    /// If `T` does not match the internal type, `None` is returned.
+    #[inline]
+    pub fn downcast_ref<T: 'static>(&self) -> Option<&T> {
+        self.as_ref().downcast_ref()
+    }
+
+    /// Downcasts the referenced data to type `&mut T`.
+    ///
+    /// If `T` does not match the internal type, `None` is returned.
+    #[inline]
+    pub fn downcast_mut<T: 'static>(&mut self) -> Option<&mut T> {
+        if !self.has_type_id::<T>() {
+            return None;
+        }
+
+        // SAFETY:
+        // raw pointer type is checked before conversion
+        Some(unsafe { &mut *(self.ptr.as_ptr() as *mut T) })
+    }
+
+    /// Converts this reference into a mutable reference of type `&'a mut T`
+    ///
+    /// If `T` does not match the internal type, this reference is returned in `Err`
+    #[inline]
+    pub fn into_mut<T: 'static>(self) -> Result<&'a mut T, Self> {
+        if !self.has_type_id::<T>() {
+            return Err(self);
+        }
+
+        // SAFETY:
+        // raw pointer type is checked before conversion
+        Ok(unsafe { &mut *(self.ptr.as_ptr() as *mut T) })
+    }
+
+    /// Returns a reference to the field found at `path`, like `position.x`
+    ///
+    /// If the path does not lead to a registered field, `None` is returned.
+    /// See [`ImposterType::field_path`].
+    #[inline]
+    pub fn field(&self, path: &str) -> Option<ImposterRef<'_>> {
+        self.as_ref().field(path)
+    }
+
+    /// Returns a mutable reference to the field found at `path`, like `position.x`
+    ///
+    /// If the path does not lead to a registered field, `None` is returned.
+    /// See [`ImposterType::field_path`].
+    #[inline]
+    pub fn field_mut(&mut self, path: &str) -> Option<ImposterMut<'_>> {
+        unsafe { ImposterMut::from_raw(self.ptr, self.ty) }
+            .into_field(path)
+            .ok()
+    }
+
+    /// Converts this reference into a mutable reference to the field found at `path`, like `position.x`
+    ///
+    /// If the path does not lead to a registered field, this reference is returned in `Err`
+    pub fn into_field(self, path: &str) -> Result<ImposterMut<'a>, Self> {
+        let Some((field, ty)) = self
+            .ty
+            .field_path(path)
+            .and_then(|field| Some((field, field.imposter_type()?)))
+        else {
+            return Err(self);
+        };
+
+        // SAFETY:
+        // registered fields are always inside the referenced data
+        unsafe {
+            let ptr = ptr::NonNull::new_unchecked(self.ptr.as_ptr().add(field.offset()));
+            Ok(ImposterMut::from_raw(ptr, ty))
+        }
+    }
+
+    /// Views the referenced data as the trait object `&mut Dyn`
+    ///
+    /// If no [`DynCaster`](crate::DynCaster) for `Dyn` was registered with the internal type, `None` is returned.
+    #[inline]
+    pub fn as_dyn_mut<Dyn: ?Sized + 'static>(&mut self) -> Option<&mut Dyn> {
+        let caster = self.ty.dyn_caster::<Dyn>()?;
+        Some(unsafe { caster.cast_mut(self.ptr.as_ptr()) })
+    }
+
+    /// Returns a mutable pointer to the referenced data
+    #[inline]
+    pub fn as_mut_ptr(&mut self) -> *mut u8 {
+        self.ptr.as_ptr()
+    }
+
+    /// Returns the [`ImposterType`] describing the referenced data
+    #[inline]
+    pub fn imposter_type(&self) -> &'static ImposterType {
+        self.ty
+    }
+
+    /// Returns the type id of the referenced data
+    #[inline]
+    pub fn type_id(&self) -> ImposterTypeId {
+        self.ty.type_id()
+    }
+
+    /// Returns the name of the referenced type
+    #[inline]
+    pub fn type_name(&self) -> &'static str {
+        self.ty.type_name()
+    }
+
+    /// Returns true if `T` matches the referenced type
+    #[inline]
+    pub fn has_type_id<T: 'static>(&self) -> bool {
+        self.ty.is::<T>()
+    }
+
+    /// Returns the layout of the referenced type
+    #[inline]
+    pub fn layout(&self) -> Layout {
+        self.ty.layout()
+    }
+}
@@ -0,0 +1,701 @@
+use std::{
+    alloc::Layout,
+    any::TypeId,
+    cmp::Ordering,
+    fmt,
+    hash::{Hash, Hasher},
+    marker::PhantomData,
+    mem, ptr,
+};
+
+use crate::{DynCaster, DynCasts, ImposterField, ImposterFields, TypeMismatch};
+
+pub type ImposterDrop = unsafe fn(ptr: *mut u8);
+
+/// Clones the value at `src` into the uninitialized memory at `dst`
+pub type ImposterClone = unsafe fn(src: *const u8, dst: *mut u8);
+
+/// Formats the value at `ptr` using its [`Debug`](fmt::Debug) implementation
+pub type ImposterDebug = unsafe fn(ptr: *const u8, f: &mut fmt::Formatter<'_>) -> fmt::Result;
+
+/// Writes the default value of a type into the uninitialized memory at `dst`
+pub type ImposterDefault = unsafe fn(dst: *mut u8);
+
+/// Returns true if the values at `a` and `b` are equal
+pub type ImposterEq = unsafe fn(a: *const u8, b: *const u8) -> bool;
+
+/// Feeds the value at `ptr` into the hasher `state`
+pub type ImposterHash = unsafe fn(ptr: *const u8, state: &mut dyn Hasher);
+
+/// Returns the [`Ordering`] between the values at `a` and `b`
+pub type ImposterCmp = unsafe fn(a: *const u8, b: *const u8) -> Ordering;
+
+/// The identity of a type described by an [`ImposterType`]
+///
+/// Rust types are identified by their [`TypeId`], while types defined at runtime
+/// are identified by a user supplied id, see [`ImposterType::runtime`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ImposterTypeId {
+    /// A rust type identified by its [`TypeId`]
+    Static(TypeId),
+    /// A type defined at runtime identified by a user supplied id
+    Runtime(u64),
+}
+
+impl From<TypeId> for ImposterTypeId {
+    #[inline]
+    fn from(type_id: TypeId) -> Self {
+        Self::Static(type_id)
+    }
+}
+
+impl ImposterTypeId {
+    /// Returns the id that identifies the rust type `T`
+    #[inline]
+    pub const fn of<T: 'static>() -> Self {
+        Self::Static(TypeId::of::<T>())
+    }
+
+    /// Returns the [`TypeId`] of the identified type as `Some(TypeId)`
+    ///
+    /// Returns `None` if this identifies a type defined at runtime
+    #[inline]
+    pub fn as_type_id(&self) -> Option<TypeId> {
+        match self {
+            Self::Static(type_id) => Some(*type_id),
+            Self::Runtime(_) => None,
+        }
+    }
+}
+
+/// The name of a type described by an [`ImposterType`]
+///
+/// The name of a rust type can only be retrieved at runtime, so it is stored as a function.
+#[derive(Clone, Copy)]
+enum TypeName {
+    Static(fn() -> &'static str),
+    Runtime(&'static str),
+}
+
+/// A static description of a type that may be stored inside an imposter.
+///
+/// A `'static` descriptor is created once for every type and is shared by
+/// every [`Imposter`](crate::Imposter), [`ImposterVec`](crate::collections::vec::ImposterVec)
+/// and [`RawMemory`](crate::RawMemory) that holds that type.
+///
+/// Descriptors may optionally record extra functionality for their type,
+/// like cloning, default construction, debug formatting, comparison, hashing, casting into trait objects
+/// or the fields of a struct.
+/// Custom descriptors can be built in a `static` using [`ImposterType::builder`],
+/// and types that are only known at runtime can be described using [`ImposterType::runtime`].
+pub struct ImposterType {
+    type_id: ImposterTypeId,
+    type_name: TypeName,
+    layout: Layout,
+    drop: Option<ImposterDrop>,
+    clone: Option<ImposterClone>,
+    debug: Option<ImposterDebug>,
+    default: Option<ImposterDefault>,
+    eq: Option<ImposterEq>,
+    hash: Option<ImposterHash>,
+    cmp: Option<ImposterCmp>,
+    dyn_casts: DynCasts,
+    fields: ImposterFields,
+}
+
+impl fmt::Debug for ImposterType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ImposterType")
+            .field("type_name", &self.type_name())
+            .field("layout", &self.layout)
+            .finish_non_exhaustive()
+    }
+}
+
+impl PartialEq for ImposterType {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.type_id == other.type_id
+    }
+}
+
+impl Eq for ImposterType {}
+
+impl ImposterType {
+    /// Returns the static [`ImposterType`] that describes `T`
+    #[inline]
+    pub const fn of<T: 'static>() -> &'static ImposterType {
+        &StaticType::<T>::TYPE
+    }
+
+    /// Returns the static [`ImposterType`] that describes `T` with a recorded clone function
+    #[inline]
+    pub const fn cloneable<T: Clone + 'static>() -> &'static ImposterType {
+        &StaticType::<T>::CLONEABLE
+    }
+
+    /// Returns the static [`ImposterType`] that describes `T` with a recorded debug formatter
+    #[inline]
+    pub const fn debuggable<T: fmt::Debug + 'static>() -> &'static ImposterType {
+        &StaticType::<T>::DEBUGGABLE
+    }
+
+    /// Returns a new [`ImposterTypeBuilder`] used to describe `T`
+    ///
+    /// # Example
+    /// ```
+    /// use imposters::{Imposter, ImposterType};
+    ///
+    /// static STRING_TYPE: ImposterType = ImposterType::builder::<String>().with_clone().build();
+    ///
+    /// let imposter = Imposter::with_type(String::from("sus"), &STRING_TYPE).unwrap();
+    /// assert!(imposter.try_clone().is_some());
+    /// ```
+    #[inline]
+    pub const fn builder<T: 'static>() -> ImposterTypeBuilder<T> {
+        ImposterTypeBuilder {
+            ty: Self::new::<T>(),
+            _type: PhantomData,
+        }
+    }
+
+    /// Returns a new descriptor for a type that is only known at runtime
+    ///
+    /// The descriptor does not record any clone, debug or trait object functionality.
+    /// Descriptors are required to be `'static`, so runtime descriptors are usually leaked using [`Box::leak`].
+    ///
+    /// The size of `layout` is padded to a multiple of its alignment,
+    /// so that items stored next to each other in collections stay aligned.
+    ///
+    /// # Safety
+    /// - every descriptor created using the same `id` must describe the same type
+    /// - `layout` must match the layout of the described type
+    /// - `drop` must be safe to call with a pointer to any valid value of the described type
+    ///
+    /// # Example
+    /// ```
+    /// use std::alloc::Layout;
+    /// use imposters::{collections::vec::ImposterVec, ImposterType};
+    ///
+    /// let layout = Layout::from_size_align(12, 4).unwrap();
+    /// let ty = unsafe { ImposterType::runtime(7, "Vec3", layout, None) };
+    /// let ty: &'static ImposterType = Box::leak(Box::new(ty));
+    ///
+    /// let mut vec = ImposterVec::with_type(ty);
+    /// let mut position = [0u8; 12];
+    /// unsafe { vec.push_raw_unchecked(position.as_mut_ptr()) };
+    /// assert_eq!(vec.type_name(), "Vec3");
+    /// ```
+    #[inline]
+    pub const unsafe fn runtime(
+        id: u64,
+        name: &'static str,
+        layout: Layout,
+        drop: Option<ImposterDrop>,
+    ) -> Self {
+        Self {
+            type_id: ImposterTypeId::Runtime(id),
+            type_name: TypeName::Runtime(name),
+            layout: layout.pad_to_align(),
+            drop,
+            clone: None,
+            debug: None,
+            default: None,
+            eq: None,
+            hash: None,
+            cmp: None,
+            dyn_casts: &[],
+            fields: &[],
+        }
+    }
+
+    const fn new<T: 'static>() -> Self {
+        Self {
+            type_id: ImposterTypeId::of::<T>(),
+            type_name: TypeName::Static(std::any::type_name::<T>),
+            layout: Layout::new::<T>(),
+            drop: match mem::needs_drop::<T>() {
+                false => None,
+                true => Some(drop_impl::<T>),
+            },
+            clone: None,
+            debug: None,
+            default: None,
+            eq: None,
+            hash: None,
+            cmp: None,
+            dyn_casts: &[],
+            fields: &[],
+        }
+    }
+
+    /// Returns the [`ImposterTypeId`] of the described type
+    #[inline]
+    pub fn type_id(&self) -> ImposterTypeId {
+        self.type_id
+    }
+
+    /// Returns the name of the described type
+    #[inline]
+    pub fn type_name(&self) -> &'static str {
+        match self.type_name {
+            TypeName::Static(type_name) => type_name(),
+            TypeName::Runtime(name) => name,
+        }
+    }
+
+    /// Returns the [`Layout`] of the described type
+    #[inline]
+    pub fn layout(&self) -> Layout {
+        self.layout
+    }
+
+    /// Returns the drop function of the described type, if it needs to be dropped
+    #[inline]
+    pub fn drop_fn(&self) -> Option<ImposterDrop> {
+        self.drop
+    }
+
+    /// Returns the clone function of the described type, if one was recorded
+    #[inline]
+    pub fn clone_fn(&self) -> Option<ImposterClone> {
+        self.clone
+    }
+
+    /// Returns the debug formatter of the described type, if one was recorded
+    #[inline]
+    pub fn debug_fn(&self) -> Option<ImposterDebug> {
+        self.debug
+    }
+
+    /// Returns the default constructor of the described type, if one was recorded
+    #[inline]
+    pub fn default_fn(&self) -> Option<ImposterDefault> {
+        self.default
+    }
+
+    /// Returns the equality function of the described type, if one was recorded
+    #[inline]
+    pub fn eq_fn(&self) -> Option<ImposterEq> {
+        self.eq
+    }
+
+    /// Returns the hash function of the described type, if one was recorded
+    #[inline]
+    pub fn hash_fn(&self) -> Option<ImposterHash> {
+        self.hash
+    }
+
+    /// Returns the ordering function of the described type, if one was recorded
+    #[inline]
+    pub fn cmp_fn(&self) -> Option<ImposterCmp> {
+        self.cmp
+    }
+
+    /// Returns the table of [`DynCaster`]s registered for the described type
+    #[inline]
+    pub fn dyn_casts(&self) -> DynCasts {
+        self.dyn_casts
+    }
+
+    /// Returns the [`DynCaster`] that casts the described type into `Dyn`, if one was registered
+    pub fn dyn_caster<Dyn: ?Sized + 'static>(&self) -> Option<&'static DynCaster<Dyn>> {
+        self.dyn_casts
+            .iter()
+            .filter_map(|caster| caster.downcast_ref::<DynCaster<Dyn>>())
+            .find(|caster| ImposterTypeId::Static(caster.type_id()) == self.type_id)
+    }
+
+    /// Returns the table of [`ImposterField`]s registered for the described type
+    #[inline]
+    pub fn fields(&self) -> ImposterFields {
+        self.fields
+    }
+
+    /// Returns the registered field called `name`, if it exists
+    pub fn field(&self, name: &str) -> Option<&'static ImposterField> {
+        self.fields
+            .iter()
+            .find(|field| field.parent_id() == self.type_id && field.name() == name)
+    }
+
+    /// Returns the field found by following a path of nested field names like `position.x`
+    ///
+    /// The offset of the returned field is relative to the start of the described type.
+    /// If any field along the path was not registered,
+    /// or was registered with a descriptor that does not describe it, `None` is returned.
+    pub fn field_path(&self, path: &str) -> Option<ImposterField> {
+        let mut names = path.split('.');
+        let mut field = *self.field(names.next()?)?;
+        for name in names {
+            field = field.imposter_type()?.field(name)?.nested_in(&field);
+        }
+
+        // the last field is accessed using its descriptor, so it has to be valid as well
+        field.imposter_type()?;
+
+        Some(field)
+    }
+
+    /// Returns true if values of the described type are stored inline inside an [`Imposter`](crate::Imposter)
+    ///
+    /// This is the case for all values that fit inside a `usize`
+    #[inline]
+    pub fn is_inline(&self) -> bool {
+        self.layout.size() <= mem::size_of::<usize>()
+            && self.layout.align() <= mem::align_of::<usize>()
+    }
+
+    /// Returns true if `T` is the described type
+    #[inline]
+    pub fn is<T: 'static>(&self) -> bool {
+        self.type_id == ImposterTypeId::of::<T>()
+    }
+
+    /// Returns a [`TypeMismatch`] between the described type and `T`
+    #[inline]
+    pub(crate) fn mismatch<T: 'static>(&self) -> TypeMismatch {
+        TypeMismatch::new(self.type_name(), std::any::type_name::<T>())
+    }
+}
+
+/// A builder used to create an [`ImposterType`] for `T` in a `const` context
+pub struct ImposterTypeBuilder<T> {
+    ty: ImposterType,
+    _type: PhantomData<fn() -> T>,
+}
+
+impl<T: 'static> ImposterTypeBuilder<T> {
+    /// Records the [`Clone`] implementation of `T`
+    #[inline]
+    pub const fn with_clone(mut self) -> Self
+    where
+        T: Clone,
+    {
+        self.ty.clone = Some(clone_impl::<T>);
+        self
+    }
+
+    /// Records the [`Debug`](fmt::Debug) implementation of `T`
+    #[inline]
+    pub const fn with_debug(mut self) -> Self
+    where
+        T: fmt::Debug,
+    {
+        self.ty.debug = Some(debug_impl::<T>);
+        self
+    }
+
+    /// Records the [`Default`] implementation of `T`
+    #[inline]
+    pub const fn with_default(mut self) -> Self
+    where
+        T: Default,
+    {
+        self.ty.default = Some(default_impl::<T>);
+        self
+    }
+
+    /// Records the [`PartialEq`] implementation of `T`
+    #[inline]
+    pub const fn with_eq(mut self) -> Self
+    where
+        T: PartialEq,
+    {
+        self.ty.eq = Some(eq_impl::<T>);
+        self
+    }
+
+    /// Records the [`Hash`] implementation of `T`
+    ///
+    /// This also records the [`Eq`] implementation so that equality stays consistent with hashing.
+    #[inline]
+    pub const fn with_hash(mut self) -> Self
+    where
+        T: Hash + Eq,
+    {
+        self.ty.hash = Some(hash_impl::<T>);
+        self.with_eq()
+    }
+
+    /// Records the [`Ord`] implementation of `T`
+    ///
+    /// This also records the [`Eq`] implementation so that equality stays consistent with ordering.
+    #[inline]
+    pub const fn with_ord(mut self) -> Self
+    where
+        T: Ord,
+    {
+        self.ty.cmp = Some(cmp_impl::<T>);
+        self.with_eq()
+    }
+
+    /// Registers the trait objects that `T` may be viewed as
+    ///
+    /// Every entry should be created using [`dyn_cast`](crate::dyn_cast) or [`DynCaster::new`].
+    /// Entries created for a type other than `T` are ignored.
+    #[inline]
+    pub const fn with_dyn(mut self, casts: DynCasts) -> Self {
+        self.ty.dyn_casts = casts;
+        self
+    }
+
+    /// Registers the fields of `T` that may be reached inside type erased data
+    ///
+    /// The table should be created using the [`imposter_fields`](crate::imposter_fields) macro.
+    /// Entries created for a type other than `T` are ignored.
+    #[inline]
+    pub const fn with_fields(mut self, fields: ImposterFields) -> Self {
+        self.ty.fields = fields;
+        self
+    }
+
+    /// Builds the final [`ImposterType`]
+    #[inline]
+    pub const fn build(self) -> ImposterType {
+        self.ty
+    }
+}
+
+/// Holder used to promote an [`ImposterType`] for `T` into static memory
+struct StaticType<T>(T);
+
+impl<T: 'static> StaticType<T> {
+    const TYPE: ImposterType = ImposterType::new::<T>();
+}
+
+impl<T: Clone + 'static> StaticType<T> {
+    const CLONEABLE: ImposterType = ImposterType::builder::<T>().with_clone().build();
+}
+
+impl<T: fmt::Debug + 'static> StaticType<T> {
+    const DEBUGGABLE: ImposterType = ImposterType::builder::<T>().with_debug().build();
+}
+
+/// Formats a type erased value using the debug formatter of its [`ImposterType`]
+///
+/// If no debug formatter was recorded, only the type name is printed.
+pub struct ErasedDebug<'a> {
+    ptr: *const u8,
+    ty: &'static ImposterType,
+    _data: PhantomData<&'a ()>,
+}
+
+impl fmt::Debug for ErasedDebug<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.ty.debug {
+            Some(debug) => unsafe { (debug)(self.ptr, f) },
+            None => f.debug_struct(self.ty.type_name()).finish_non_exhaustive(),
+        }
+    }
+}
+
+impl ErasedDebug<'_> {
+    /// # Safety
+    /// `ptr` must point to a valid value described by `ty` for the lifetime of this struct
+    #[inline]
+    pub(crate) unsafe fn new(ptr: *const u8, ty: &'static ImposterType) -> Self {
+        Self {
+            ptr,
+            ty,
+            _data: PhantomData,
+        }
+    }
+}
+
+/// This is the function used if data needs to be dropped inside a imposter
+#[inline]
+unsafe fn drop_impl<T>(ptr: *mut u8) {
+    ptr::drop_in_place(ptr as *mut T);
+}
+
+/// This is the function used if data needs to be formatted inside a imposter
+#[inline]
+unsafe fn debug_impl<T: fmt::Debug>(ptr: *const u8, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    fmt::Debug::fmt(&*(ptr as *const T), f)
+}
+
+/// This is the function used if data needs to be cloned inside a imposter
+#[inline]
+unsafe fn clone_impl<T: Clone>(src: *const u8, dst: *mut u8) {
+    (dst as *mut T).write((*(src as *const T)).clone());
+}
+
+/// This is the function used if data needs to be default constructed inside a imposter
+#[inline]
+unsafe fn default_impl<T: Default>(dst: *mut u8) {
+    (dst as *mut T).write(T::default());
+}
+
+/// This is the function used if data needs to be compared for equality inside a imposter
+#[inline]
+unsafe fn eq_impl<T: PartialEq>(a: *const u8, b: *const u8) -> bool {
+    *(a as *const T) == *(b as *const T)
+}
+
+/// This is the function used if data needs to be hashed inside a imposter
+#[inline]
+unsafe fn hash_impl<T: Hash>(ptr: *const u8, mut state: &mut dyn Hasher) {
+    (*(ptr as *const T)).hash(&mut state);
+}
+
+/// This is the function used if data needs to be ordered inside a imposter
+#[inline]
+unsafe fn cmp_impl<T: Ord>(a: *const u8, b: *const u8) -> Ordering {
+    (*(a as *const T)).cmp(&*(b as *const T))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn static_type() {
+        let ty = ImposterType::of::<String>();
+        assert!(ty.is::<String>());
+        assert!(!ty.is::<u32>());
+        assert!(ty.drop_fn().is_some());
+        assert!(ImposterType::of::<u32>().drop_fn().is_none());
+        assert_eq!(ty.layout(), Layout::new::<String>());
+        assert_eq!(ty, ImposterType::of::<String>());
+        assert!(ty.clone_fn().is_none());
+        assert!(ty.debug_fn().is_none());
+    }
+
+    #[test]
+    fn dyn_type() {
+        static TYPE: ImposterType = ImposterType::builder::<u32>()
+            .with_dyn(&[
+                crate::dyn_cast!(u32 as dyn fmt::Display),
+                crate::dyn_cast!(u64 as dyn fmt::Debug),
+            ])
+            .build();
+
+        assert!(TYPE.dyn_caster::<dyn fmt::Display>().is_some());
+        assert!(TYPE.dyn_caster::<dyn fmt::Debug>().is_none());
+        assert!(ImposterType::of::<u32>()
+            .dyn_caster::<dyn fmt::Display>()
+            .is_none());
+    }
+
+    #[test]
+    fn debuggable_type() {
+        let value = 42u32;
+        let ty = ImposterType::debuggable::<u32>();
+        let debug = unsafe { ErasedDebug::new(&value as *const u32 as *const u8, ty) };
+        assert_eq!(format!("{debug:?}"), "42");
+
+        let debug = unsafe {
+            ErasedDebug::new(&value as *const u32 as *const u8, ImposterType::of::<u32>())
+        };
+        assert_eq!(format!("{debug:?}"), "u32 { .. }");
+    }
+
+    #[test]
+    fn cloneable_type() {
+        static BUILT: ImposterType = ImposterType::builder::<String>().with_clone().build();
+        assert!(ImposterType::cloneable::<String>().clone_fn().is_some());
+        assert!(BUILT.clone_fn().is_some());
+        assert_eq!(&BUILT, ImposterType::of::<String>());
+    }
+
+    #[test]
+    fn default_type() {
+        static TYPE: ImposterType = ImposterType::builder::<String>().with_default().build();
+        assert!(ImposterType::of::<String>().default_fn().is_none());
+
+        let mut value = mem::MaybeUninit::<String>::uninit();
+        unsafe { (TYPE.default_fn().unwrap())(value.as_mut_ptr() as *mut u8) };
+        assert_eq!(unsafe { value.assume_init() }, "");
+    }
+
+    #[test]
+    fn comparable_type() {
+        static HASHED: ImposterType = ImposterType::builder::<String>().with_hash().build();
+        static ORDERED: ImposterType = ImposterType::builder::<u32>().with_ord().build();
+        assert!(HASHED.hash_fn().is_some() && HASHED.eq_fn().is_some());
+        assert!(HASHED.cmp_fn().is_none());
+        assert!(ORDERED.cmp_fn().is_some() && ORDERED.eq_fn().is_some());
+
+        let (a, b) = (1u32, 2u32);
+        let cmp = ORDERED.cmp_fn().unwrap();
+        let ordering = unsafe { cmp(&a as *const u32 as *const u8, &b as *const u32 as *const u8) };
+        assert_eq!(ordering, Ordering::Less);
+    }
+
+    #[test]
+    fn runtime_type() {
+        let layout = Layout::new::<u32>();
+        let ty = unsafe { ImposterType::runtime(42, "Sus", layout, None) };
+        assert_eq!(ty.type_id(), ImposterTypeId::Runtime(42));
+        assert_eq!(ty.type_id().as_type_id(), None);
+        assert_eq!(ty.type_name(), "Sus");
+        assert!(!ty.is::<u32>());
+        assert_ne!(&ty, ImposterType::of::<u32>());
+        assert_eq!(ty, unsafe {
+            ImposterType::runtime(42, "Sus", layout, None)
+        });
+
+        let layout = Layout::from_size_align(12, 8).unwrap();
+        let ty = unsafe { ImposterType::runtime(7, "Padded", layout, None) };
+        assert_eq!(ty.layout(), Layout::from_size_align(16, 8).unwrap());
+        assert_eq!(
+            ImposterType::of::<u32>().type_id().as_type_id(),
+            Some(TypeId::of::<u32>())
+        );
+    }
+
+    #[test]
+    fn field_type() {
+        struct Vec2 {
+            x: f32,
+            y: f32,
+        }
+
+        struct Transform {
+            scale: f64,
+            position: Vec2,
+        }
+
+        static VEC2: ImposterType = ImposterType::builder::<Vec2>()
+            .with_fields(crate::imposter_fields!(Vec2 { x: f32, y: f32 }))
+            .build();
+        static TRANSFORM: ImposterType = ImposterType::builder::<Transform>()
+            .with_fields(crate::imposter_fields!(Transform {
+                scale: f64,
+                position: Vec2 = &VEC2,
+            }))
+            .build();
+        static WRONG: ImposterType = ImposterType::builder::<Transform>()
+            .with_fields(crate::imposter_fields!(Transform {
+                position: Vec2 = &TRANSFORM
+            }))
+            .build();
+        static FOREIGN: ImposterType = ImposterType::builder::<u32>()
+            .with_fields(crate::imposter_fields!(Vec2 { x: f32 }))
+            .build();
+
+        assert_eq!(TRANSFORM.fields().len(), 2);
+        assert!(TRANSFORM
+            .field("scale")
+            .unwrap()
+            .imposter_type()
+            .unwrap()
+            .is::<f64>());
+        let y = TRANSFORM.field_path("position.y").unwrap();
+        assert_eq!(y.name(), "y");
+        assert_eq!(
+            y.offset(),
+            mem::offset_of!(Transform, position) + mem::offset_of!(Vec2, y)
+        );
+        assert!(y.imposter_type().unwrap().is::<f32>());
+        assert!(TRANSFORM.field_path("position.z").is_none());
+        assert!(TRANSFORM.field_path("scale.x").is_none());
+        assert!(TRANSFORM.field_path("").is_none());
+        assert!(FOREIGN.field("x").is_none());
+
+        assert!(WRONG.field("position").unwrap().imposter_type().is_none());
+        assert!(WRONG.field_path("position").is_none());
+        assert!(WRONG.field_path("position.x").is_none());
+    }
+}
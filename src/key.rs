@@ -0,0 +1,55 @@
+use std::{
+    hash::{Hash, Hasher},
+    ops::Deref,
+};
+
+/// An [`Imposter`](crate::Imposter) or [`ImposterRef`](crate::ImposterRef) that can be used as a key in hash maps and sets
+///
+/// Keys are created using [`Imposter::into_key`](crate::Imposter::into_key) or [`ImposterRef::into_key`](crate::ImposterRef::into_key),
+/// which only succeed if a hash function was recorded for the internal type.
+/// Hashing is only recorded for types that implement [`Eq`],
+/// see [`ImposterTypeBuilder::with_hash`](crate::ImposterTypeBuilder::with_hash),
+/// so keys implement [`Eq`] even though imposters only implement [`PartialEq`].
+#[derive(Debug, Clone, Copy)]
+pub struct ImposterKey<T>(T);
+
+impl<T: PartialEq> PartialEq for ImposterKey<T> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl<T: PartialEq> Eq for ImposterKey<T> {}
+
+impl<T: Hash> Hash for ImposterKey<T> {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.hash(state)
+    }
+}
+
+impl<T> Deref for ImposterKey<T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> ImposterKey<T> {
+    /// Wraps `item` as a key
+    ///
+    /// The caller must ensure that a hash function was recorded for the internal type of `item`.
+    #[inline]
+    pub(crate) fn new_unchecked(item: T) -> Self {
+        Self(item)
+    }
+
+    /// Returns the wrapped imposter or reference
+    #[inline]
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
@@ -1,9 +1,35 @@
 #![cfg_attr(miri, feature(alloc_layout_extra))]
 
+mod allocator;
+mod dyn_cast;
+mod error;
+mod field;
 mod imposter;
+mod imposter_ref;
+mod imposter_type;
+mod key;
 mod memory;
+mod pinned;
+mod registry;
+mod scoped;
+mod serialize;
+mod shared;
+mod sync;
 
 pub mod collections;
 
+pub use crate::allocator::*;
+pub use crate::dyn_cast::*;
+pub use crate::error::*;
+pub use crate::field::*;
 pub use crate::imposter::*;
+pub use crate::imposter_ref::*;
+pub use crate::imposter_type::*;
+pub use crate::key::*;
 pub use memory::*;
+pub use pinned::*;
+pub use registry::*;
+pub use scoped::*;
+pub use serialize::*;
+pub use shared::*;
+pub use sync::*;
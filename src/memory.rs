@@ -1,31 +1,29 @@
 use std::{
-    alloc::{alloc, dealloc, handle_alloc_error, realloc, Layout},
+    alloc::{handle_alloc_error, GlobalAlloc, Layout},
     ptr::{self, NonNull},
 };
 
+use crate::{Global, ImposterDrop, ImposterType, TryReserveError};
+
 /// A bare bones memory management abstraction for the imposters library
+///
+/// All memory is allocated using the allocator `A`, which defaults to the [`Global`] allocator.
 #[derive(Debug)]
-pub struct RawMemory {
+pub struct RawMemory<A: GlobalAlloc = Global> {
     ptr: ptr::NonNull<u8>,
     capacity: usize,
-    element_layout: Layout,
+    ty: &'static ImposterType,
+    alloc: A,
 }
 
-impl Drop for RawMemory {
+impl<A: GlobalAlloc> Drop for RawMemory<A> {
     #[inline]
     fn drop(&mut self) {
         if self.capacity == 0 {
             return;
         }
 
-        let array_size = self.element_layout.size() * self.capacity;
-        let array_align = self.element_layout.align();
-        unsafe {
-            dealloc(
-                self.ptr.as_ptr(),
-                Layout::from_size_align_unchecked(array_size, array_align),
-            );
-        }
+        unsafe { self.alloc.dealloc(self.ptr.as_ptr(), self.memory_layout()) };
     }
 }
 
@@ -33,20 +31,31 @@ impl RawMemory {
     /// Returns a new RawMemory struct that should hold items of type `T`
     #[inline]
     pub fn new<T: 'static>() -> Self {
-        Self {
-            ptr: ptr::NonNull::<T>::dangling().cast(),
-            capacity: 0,
-            element_layout: Layout::new::<T>(),
-        }
+        Self::from_type(ImposterType::of::<T>())
     }
 
-    /// Returns a new RawMemory struct with a given item `layout`
+    /// Returns a new RawMemory struct that should hold items described by `ty`
     #[inline]
-    pub fn with_element_layout(layout: Layout) -> Self {
+    pub fn from_type(ty: &'static ImposterType) -> Self {
+        Self::from_type_in(ty, Global)
+    }
+}
+
+impl<A: GlobalAlloc> RawMemory<A> {
+    /// Returns a new RawMemory struct that should hold items of type `T`, allocated using `alloc`
+    #[inline]
+    pub fn new_in<T: 'static>(alloc: A) -> Self {
+        Self::from_type_in(ImposterType::of::<T>(), alloc)
+    }
+
+    /// Returns a new RawMemory struct that should hold items described by `ty`, allocated using `alloc`
+    #[inline]
+    pub fn from_type_in(ty: &'static ImposterType, alloc: A) -> Self {
         Self {
-            ptr: Self::create_dangling_ptr(&layout),
+            ptr: create_dangling_ptr(&ty.layout()),
             capacity: 0,
-            element_layout: layout,
+            ty,
+            alloc,
         }
     }
 
@@ -56,7 +65,7 @@ impl RawMemory {
     /// `index` must be in bounds
     #[inline]
     pub unsafe fn index_ptr_unchecked(&self, index: usize) -> *mut u8 {
-        self.ptr().add(index * self.element_layout.size())
+        self.ptr().add(index * self.element_layout().size())
     }
 
     /// Copies data from `src` into the given `index`
@@ -67,21 +76,28 @@ impl RawMemory {
     #[inline]
     pub unsafe fn copy_to_index_unchecked(&mut self, src: *const u8, index: usize) {
         let index_ptr = self.index_ptr_unchecked(index);
-        ptr::copy_nonoverlapping(src, index_ptr, self.element_layout.size())
+        ptr::copy_nonoverlapping(src, index_ptr, self.element_layout().size())
     }
 
     /// Allocates new memory and copies the item at `index` to that location
     ///
+    /// The new memory is allocated using this memory's allocator, see [`RawMemory::allocator`].
+    ///
     /// # Safety
     /// `index` must be in bounds
     #[inline]
     pub unsafe fn copy_to_alloc_unchecked(&self, index: usize) -> ptr::NonNull<u8> {
-        let index_ptr = self.ptr.as_ptr().add(index * self.element_layout.size());
-        let new_ptr = alloc(self.element_layout);
+        let layout = self.element_layout();
+        if layout.size() == 0 {
+            return create_dangling_ptr(&layout);
+        }
+
+        let index_ptr = self.index_ptr_unchecked(index);
+        let new_ptr = self.alloc.alloc(layout);
         if new_ptr.is_null() {
-            handle_alloc_error(self.element_layout);
+            handle_alloc_error(layout);
         }
-        ptr::copy_nonoverlapping(index_ptr, new_ptr, self.element_layout.size());
+        ptr::copy_nonoverlapping(index_ptr, new_ptr, layout.size());
         NonNull::new_unchecked(new_ptr)
     }
 
@@ -95,7 +111,7 @@ impl RawMemory {
             return;
         }
 
-        let element_size = self.element_layout.size();
+        let element_size = self.element_layout().size();
         let array_ptr = self.ptr();
         ptr::swap_nonoverlapping(
             array_ptr.add(x * element_size),
@@ -104,39 +120,83 @@ impl RawMemory {
         );
     }
 
+    /// Drops the items from `start` up to `end` using the drop function of the element type
+    ///
+    /// If dropping an item panics, the remaining items are still dropped before unwinding continues,
+    /// just like dropping a slice. A second panic while unwinding aborts the process.
+    ///
+    /// # Safety
+    /// - every index in `start..end` must be in bounds and contain a valid item
+    /// - the dropped items must not be used again after calling this
+    pub unsafe fn drop_range_unchecked(&mut self, start: usize, end: usize) {
+        let Some(drop) = self.ty.drop_fn() else {
+            return;
+        };
+
+        let mut range = DropRange {
+            ptr: self.ptr(),
+            size: self.element_layout().size(),
+            index: start,
+            end,
+            drop,
+        };
+        range.drop_remaining();
+    }
+
     /// Resizes this block of memory to match `new_capacity`
     ///
     /// If shrinking, this will technically forget the items at the end of the memory.
     /// Those items will not be dropped. While this may be unfavorable it is not technically undefined
     /// as [`std::mem::forget`] is also marked as safe.
+    ///
+    /// # Panics
+    /// Panics if the new memory size overflows, and aborts if the allocation fails.
+    /// See [`RawMemory::try_resize`] for a fallible version.
     pub fn resize(&mut self, new_capacity: usize) {
-        if self.capacity == new_capacity || self.element_layout.size() == 0 {
-            return;
+        match self.try_resize(new_capacity) {
+            Ok(()) => (),
+            Err(TryReserveError::CapacityOverflow) => panic!("memory overflow"),
+            Err(TryReserveError::AllocError(layout)) => handle_alloc_error(layout),
+        }
+    }
+
+    /// Resizes this block of memory to match `new_capacity`, returning `Ok(())`
+    ///
+    /// If the new memory size overflows or the allocation fails, a [`TryReserveError`]
+    /// is returned and this block of memory is left unchanged.
+    /// Shrinking forgets items just like [`RawMemory::resize`].
+    pub fn try_resize(&mut self, new_capacity: usize) -> Result<(), TryReserveError> {
+        if self.capacity == new_capacity || self.element_layout().size() == 0 {
+            return Ok(());
         }
 
         let old_memory_layout = self.memory_layout();
         self.ptr = if new_capacity == 0 {
-            unsafe { dealloc(self.ptr(), old_memory_layout) };
-            Self::create_dangling_ptr(&self.element_layout)
+            unsafe { self.alloc.dealloc(self.ptr(), old_memory_layout) };
+            create_dangling_ptr(&self.element_layout())
         } else {
-            let new_memory_size = self
-                .element_layout
+            let new_memory_layout = self
+                .element_layout()
                 .size()
                 .checked_mul(new_capacity)
-                .expect("memory overflow");
+                .and_then(|size| Layout::from_size_align(size, self.element_layout().align()).ok())
+                .ok_or(TryReserveError::CapacityOverflow)?;
             unsafe {
-                let new_memory_layout =
-                    Layout::from_size_align_unchecked(new_memory_size, self.element_layout.align());
                 if self.capacity == 0 {
-                    ptr::NonNull::new(alloc(new_memory_layout))
+                    ptr::NonNull::new(self.alloc.alloc(new_memory_layout))
                 } else {
-                    ptr::NonNull::new(realloc(self.ptr(), old_memory_layout, new_memory_size))
+                    ptr::NonNull::new(self.alloc.realloc(
+                        self.ptr(),
+                        old_memory_layout,
+                        new_memory_layout.size(),
+                    ))
                 }
-                .unwrap_or_else(|| handle_alloc_error(new_memory_layout))
+                .ok_or(TryReserveError::AllocError(new_memory_layout))?
             }
         };
 
         self.capacity = new_capacity;
+        Ok(())
     }
 
     /// Returns a pointer to the beginning of this memory block
@@ -151,10 +211,22 @@ impl RawMemory {
         self.capacity
     }
 
+    /// Returns a reference to the allocator used by this memory block
+    #[inline]
+    pub fn allocator(&self) -> &A {
+        &self.alloc
+    }
+
+    /// Returns the [`ImposterType`] of the elements in this memory block
+    #[inline]
+    pub fn imposter_type(&self) -> &'static ImposterType {
+        self.ty
+    }
+
     /// Returns the associated element layout of this memory block
     #[inline]
     pub fn element_layout(&self) -> Layout {
-        self.element_layout
+        self.ty.layout()
     }
 
     /// Returns the layout for the entirety of this memory block
@@ -162,33 +234,84 @@ impl RawMemory {
     pub fn memory_layout(&self) -> Layout {
         unsafe {
             Layout::from_size_align_unchecked(
-                self.element_layout
+                self.element_layout()
                     .size()
                     .checked_mul(self.capacity)
                     .expect("memory overflow"),
-                self.element_layout.align(),
+                self.element_layout().align(),
             )
         }
     }
+}
+
+/// Creates a dangling pointer with a specified layout.
+/// This is abstracted to allow for MIRI to make smarter pointer checks.
+///
+/// # Safety
+/// This pointer is dangling and invalid.
+/// This is not inherently unsafe, unless the pointer is dereferenced.
+/// This pointer should only be used to `alloc` new memory with the same alignment.
+#[inline]
+pub(crate) fn create_dangling_ptr(layout: &Layout) -> ptr::NonNull<u8> {
+    #[cfg(miri)]
+    {
+        // Use special miri dangling pointer
+        // this allows miri to track dangling pointers better
+        layout.dangling()
+    }
+    #[cfg(not(miri))]
+    unsafe {
+        ptr::NonNull::new_unchecked(layout.align() as *mut u8)
+    }
+}
+
+/// Deallocates memory when dropped, used to release memory when initialization or dropping panics
+///
+/// Zero sized layouts are never allocated, so nothing is deallocated for them.
+pub(crate) struct DeallocGuard<'a, A: GlobalAlloc> {
+    pub(crate) ptr: *mut u8,
+    pub(crate) layout: Layout,
+    pub(crate) alloc: &'a A,
+}
+
+impl<A: GlobalAlloc> Drop for DeallocGuard<'_, A> {
+    #[inline]
+    fn drop(&mut self) {
+        if self.layout.size() != 0 {
+            unsafe { self.alloc.dealloc(self.ptr, self.layout) };
+        }
+    }
+}
+
+/// A range of items that still have to be dropped
+///
+/// If a drop function panics, the remaining items are dropped when this is dropped while unwinding.
+struct DropRange {
+    ptr: *mut u8,
+    size: usize,
+    index: usize,
+    end: usize,
+    drop: ImposterDrop,
+}
 
-    /// Creates a dangling pointer with a specified layout.
-    /// This is abstracted to allow for MIRI to make smarter pointer checks.
+impl DropRange {
+    /// Drops every remaining item in the range
     ///
     /// # Safety
-    /// This pointer is dangling and invalid.
-    /// This is not inherently unsafe, unless the pointer is dereferenced.
-    /// This pointer should only be used to `alloc` new memory with the same alignment.
-    #[inline]
-    fn create_dangling_ptr(layout: &Layout) -> ptr::NonNull<u8> {
-        #[cfg(miri)]
-        {
-            // Use special miri dangling pointer
-            // this allows miri to track dangling pointers better
-            layout.dangling()
-        }
-        #[cfg(not(miri))]
-        unsafe {
-            ptr::NonNull::new_unchecked(layout.align() as *mut u8)
+    /// every remaining item must be valid and never used again
+    unsafe fn drop_remaining(&mut self) {
+        while self.index < self.end {
+            let item = self.ptr.add(self.index * self.size);
+            // advance first so that a panicking item is never dropped again
+            self.index += 1;
+            (self.drop)(item);
         }
     }
 }
+
+impl Drop for DropRange {
+    #[inline]
+    fn drop(&mut self) {
+        unsafe { self.drop_remaining() }
+    }
+}
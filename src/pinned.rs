@@ -0,0 +1,265 @@
+use std::{
+    alloc::{GlobalAlloc, Layout},
+    fmt,
+    mem::ManuallyDrop,
+    pin::Pin,
+    ptr,
+};
+
+use crate::{
+    memory::DeallocGuard, ErasedDebug, Global, Imposter, ImposterRef, ImposterType, ImposterTypeId,
+    Rejected,
+};
+
+/// A type erased wrapper around pinned data that never moves in memory
+///
+/// Unlike [`Imposter`], the data is always stored on the heap, even if it could be stored inline.
+/// A pinned imposter can never be turned back into an [`Imposter`] or pushed into a collection,
+/// so the data is guaranteed to stay in place until it is dropped.
+/// This makes it suitable for futures and other self referential types.
+///
+/// # Example
+/// ```
+/// use std::marker::PhantomPinned;
+/// use imposters::Imposter;
+///
+/// struct Node(PhantomPinned);
+///
+/// let mut pinned = Imposter::new(Node(PhantomPinned)).into_pin();
+/// let node = pinned.downcast_pin_mut::<Node>().unwrap();
+/// ```
+pub struct PinnedImposter<A: GlobalAlloc = Global> {
+    ptr: ptr::NonNull<u8>,
+    ty: &'static ImposterType,
+    alloc: A,
+}
+
+impl<A: GlobalAlloc> Drop for PinnedImposter<A> {
+    fn drop(&mut self) {
+        unsafe {
+            // release the memory even if the drop function panics
+            let _guard = (self.layout().size() != 0).then(|| DeallocGuard {
+                ptr: self.ptr.as_ptr(),
+                layout: self.layout(),
+                alloc: &self.alloc,
+            });
+
+            if let Some(drop) = self.ty.drop_fn() {
+                (drop)(self.ptr.as_ptr());
+            }
+        }
+    }
+}
+
+impl<A: GlobalAlloc> fmt::Debug for PinnedImposter<A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.ty.debug_fn().is_some() {
+            return self.debug_value().fmt(f);
+        }
+
+        f.debug_struct("PinnedImposter")
+            .field("type_name", &self.type_name())
+            .field("data", &self.ptr)
+            .finish()
+    }
+}
+
+impl<A: GlobalAlloc> From<Imposter<A>> for PinnedImposter<A> {
+    #[inline]
+    fn from(imposter: Imposter<A>) -> Self {
+        let (ptr, ty, alloc) = imposter.into_raw_parts_with_alloc();
+        Self { ptr, ty, alloc }
+    }
+}
+
+impl PinnedImposter {
+    /// Creates a new pinned imposter containing `item`
+    #[inline]
+    pub fn new<T: 'static>(item: T) -> Self {
+        Imposter::new(item).into()
+    }
+}
+
+impl<A: GlobalAlloc> PinnedImposter<A> {
+    /// Creates a new pinned imposter containing `item`, allocated using `alloc`
+    #[inline]
+    pub fn new_in<T: 'static>(item: T, alloc: A) -> Self {
+        Imposter::new_in(item, alloc).into()
+    }
+
+    /// Downcasts the data in this imposter to an owned type `T`.
+    ///
+    /// Only types that implement [`Unpin`] may be moved out of a pinned imposter.
+    /// If `T` does not match the internal type, the imposter is returned in `Err`
+    pub fn downcast<T: Unpin + 'static>(self) -> Result<T, Rejected<Self>> {
+        if !self.has_type_id::<T>() {
+            let error = self.ty.mismatch::<T>();
+            return Err(Rejected::new(self, error));
+        }
+
+        let mut imposter = ManuallyDrop::new(self);
+        // SAFETY:
+        // the type is checked above, and `T` may be moved because it is `Unpin`
+        unsafe {
+            let item = ptr::read(imposter.ptr.as_ptr() as *const T);
+            imposter.dealloc();
+            ptr::drop_in_place(&mut imposter.alloc);
+            Ok(item)
+        }
+    }
+
+    /// Downcasts the data in this imposter to type `&T`.
+    ///
+    /// If `T` does not match the internal type, `None` is returned.
+    #[inline]
+    pub fn downcast_ref<T: 'static>(&self) -> Option<&T> {
+        self.as_imposter_ref().downcast_ref()
+    }
+
+    /// Downcasts the data in this imposter to type `&mut T`.
+    ///
+    /// Only types that implement [`Unpin`] may be borrowed mutably without pinning.
+    /// If `T` does not match the internal type, `None` is returned.
+    #[inline]
+    pub fn downcast_mut<T: Unpin + 'static>(&mut self) -> Option<&mut T> {
+        self.downcast_pin_mut().map(Pin::get_mut)
+    }
+
+    /// Downcasts the data in this imposter to type `Pin<&mut T>`.
+    ///
+    /// If `T` does not match the internal type, `None` is returned.
+    #[inline]
+    pub fn downcast_pin_mut<T: 'static>(&mut self) -> Option<Pin<&mut T>> {
+        if !self.has_type_id::<T>() {
+            return None;
+        }
+
+        // SAFETY:
+        // the type is checked above, and the data is never moved until it is dropped
+        Some(unsafe { Pin::new_unchecked(&mut *(self.ptr.as_ptr() as *mut T)) })
+    }
+
+    /// Deallocates the data this imposter points to
+    ///
+    /// # Safety
+    /// The data must not be used again after calling this
+    #[inline]
+    unsafe fn dealloc(&self) {
+        if self.layout().size() != 0 {
+            self.alloc.dealloc(self.ptr.as_ptr(), self.layout());
+        }
+    }
+
+    /// Returns a borrowed [`ImposterRef`] to the internal data
+    #[inline]
+    pub fn as_imposter_ref(&self) -> ImposterRef<'_> {
+        unsafe { ImposterRef::from_raw(self.ptr, self.ty) }
+    }
+
+    /// Returns an [`ErasedDebug`] that formats the internal data
+    #[inline]
+    pub fn debug_value(&self) -> ErasedDebug<'_> {
+        self.as_imposter_ref().debug_value()
+    }
+
+    /// Returns a pointer to the internal data, which stays valid until this imposter is dropped
+    #[inline]
+    pub fn data(&self) -> ptr::NonNull<u8> {
+        self.ptr
+    }
+
+    /// Returns a reference to the allocator used by this imposter
+    #[inline]
+    pub fn allocator(&self) -> &A {
+        &self.alloc
+    }
+
+    /// Returns the [`ImposterType`] describing the internal data
+    #[inline]
+    pub fn imposter_type(&self) -> &'static ImposterType {
+        self.ty
+    }
+
+    /// Returns the type id of the internal data
+    #[inline]
+    pub fn type_id(&self) -> ImposterTypeId {
+        self.ty.type_id()
+    }
+
+    /// Returns the name of the internal type
+    #[inline]
+    pub fn type_name(&self) -> &'static str {
+        self.ty.type_name()
+    }
+
+    /// Returns true if `T` matches the internal type
+    #[inline]
+    pub fn has_type_id<T: 'static>(&self) -> bool {
+        self.ty.is::<T>()
+    }
+
+    /// Returns the layout of the internal type
+    #[inline]
+    pub fn layout(&self) -> Layout {
+        self.ty.layout()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::Cell, marker::PhantomPinned, rc::Rc};
+
+    use super::*;
+
+    /// Records its own address when polled, and checks it on drop
+    struct Anchor {
+        addr: Rc<Cell<usize>>,
+        _pin: PhantomPinned,
+    }
+
+    impl Anchor {
+        fn poll(self: Pin<&mut Self>) {
+            self.addr.set(&*self as *const Self as usize);
+        }
+    }
+
+    impl Drop for Anchor {
+        fn drop(&mut self) {
+            assert_eq!(self.addr.get(), self as *const Self as usize);
+        }
+    }
+
+    #[test]
+    fn pinned_imposter() {
+        let addr = Rc::new(Cell::new(0));
+        let mut pinned = Imposter::new(Anchor {
+            addr: addr.clone(),
+            _pin: PhantomPinned,
+        })
+        .into_pin();
+
+        pinned.downcast_pin_mut::<Anchor>().unwrap().poll();
+        assert_eq!(addr.get(), pinned.data().as_ptr() as usize);
+        assert!(pinned.downcast_pin_mut::<u32>().is_none());
+
+        let moved = pinned;
+        assert_eq!(addr.get(), moved.data().as_ptr() as usize);
+        drop(moved);
+    }
+
+    #[test]
+    fn pinned_inline_imposter() {
+        let rc = Rc::new(42);
+        let mut pinned = PinnedImposter::new(rc.clone());
+        assert_eq!(**pinned.downcast_ref::<Rc<i32>>().unwrap(), 42);
+        assert!(pinned.downcast_mut::<u32>().is_none());
+        *pinned.downcast_mut::<Rc<i32>>().unwrap() = rc.clone();
+        assert_eq!(Rc::strong_count(&rc), 2);
+        let pinned = pinned.downcast::<u32>().unwrap_err().into_value();
+        assert_eq!(pinned.downcast::<Rc<i32>>().unwrap(), rc);
+
+        drop(PinnedImposter::new(rc.clone()));
+        assert_eq!(Rc::strong_count(&rc), 1);
+        PinnedImposter::new(()).downcast::<()>().unwrap();
+    }
+}
@@ -0,0 +1,509 @@
+use std::{
+    alloc::GlobalAlloc,
+    collections::HashMap,
+    io::{Read, Write},
+};
+
+use crate::{
+    collections::vec::ImposterVec,
+    serialize::{
+        deserialize_erased, invalid_data, read_bytes, read_items_len, read_len, serialize_erased,
+        write_len,
+    },
+    Global, Imposter, ImposterDeserialize, ImposterRef, ImposterSerialize, ImposterType,
+    ImposterTypeId, Serializable, SerializeError, TypeMismatch,
+};
+
+/// A type registered with a [`TypeRegistry`]
+#[derive(Debug, Clone, Copy)]
+pub struct TypeRegistration {
+    ty: &'static ImposterType,
+    serialize: Option<ImposterSerialize>,
+    deserialize: Option<ImposterDeserialize>,
+}
+
+impl TypeRegistration {
+    /// Returns the [`ImposterType`] describing the registered type
+    #[inline]
+    pub fn imposter_type(&self) -> &'static ImposterType {
+        self.ty
+    }
+
+    /// Returns the serialize function of the registered type if it exists
+    #[inline]
+    pub fn serialize_fn(&self) -> Option<ImposterSerialize> {
+        self.serialize
+    }
+
+    /// Returns the deserialize function of the registered type if it exists
+    #[inline]
+    pub fn deserialize_fn(&self) -> Option<ImposterDeserialize> {
+        self.deserialize
+    }
+}
+
+/// A registry of types identified by a stable name
+///
+/// Rust type names and type ids may change between compilations,
+/// so data that is stored on disk has to refer to its types using a name that never changes.
+/// The registry maps each name to an [`ImposterType`] and back, so empty collections and
+/// default values may be created from a name alone, see [`ImposterVec::from_registered`]
+/// and [`Imposter::default_for`].
+///
+/// Types that are registered along with their [`Serializable`] implementation
+/// can also be written to and read back from a compact binary format.
+///
+/// Registering a name or a type again replaces its previous registration.
+///
+/// # Format
+/// - a type name is written as a `u64` byte length followed by its utf-8 bytes
+/// - an imposter is written as its type name followed by its value
+/// - a vec is written as its type name, a `u64` length and then each of its items
+///
+/// # Example
+/// ```
+/// use imposters::{collections::vec::ImposterVec, TypeRegistry};
+///
+/// let mut registry = TypeRegistry::new();
+/// registry.register::<String>("name");
+///
+/// let mut vec = ImposterVec::new::<String>();
+/// vec.push_item(String::from("sus")).unwrap();
+///
+/// let mut bytes = Vec::new();
+/// registry.serialize_vec(&vec, &mut bytes).unwrap();
+/// let vec = registry.deserialize_vec(bytes.as_slice()).unwrap();
+/// assert_eq!(vec.as_slice::<String>().unwrap(), ["sus"]);
+/// ```
+#[derive(Debug, Default)]
+pub struct TypeRegistry {
+    types: HashMap<Box<str>, TypeRegistration>,
+    names: HashMap<ImposterTypeId, Box<str>>,
+}
+
+impl TypeRegistry {
+    /// Creates a new empty registry
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers the serializable type `T` using the stable `name`
+    #[inline]
+    pub fn register<T: Serializable + 'static>(&mut self, name: impl Into<Box<str>>) {
+        self.insert(
+            name.into(),
+            Self::serializable::<T>(ImposterType::of::<T>()),
+        );
+    }
+
+    /// Registers the serializable type `T` described by `ty` using the stable `name`
+    ///
+    /// Data read from this registry will use `ty`, so any recorded functions like
+    /// [`ImposterType::clone_fn`] are available on the deserialized values.
+    /// If `ty` does not describe `T`, `Err(TypeMismatch)` is returned.
+    #[inline]
+    pub fn register_with_type<T: Serializable + 'static>(
+        &mut self,
+        name: impl Into<Box<str>>,
+        ty: &'static ImposterType,
+    ) -> Result<(), TypeMismatch> {
+        if !ty.is::<T>() {
+            return Err(ty.mismatch::<T>());
+        }
+
+        self.insert(name.into(), Self::serializable::<T>(ty));
+        Ok(())
+    }
+
+    /// Registers the type described by `ty` using the stable `name`
+    ///
+    /// This also works for types defined at runtime, see [`ImposterType::runtime`].
+    /// Types registered this way can not be serialized.
+    #[inline]
+    pub fn register_type(&mut self, name: impl Into<Box<str>>, ty: &'static ImposterType) {
+        let registration = TypeRegistration {
+            ty,
+            serialize: None,
+            deserialize: None,
+        };
+        self.insert(name.into(), registration);
+    }
+
+    #[inline]
+    fn serializable<T: Serializable>(ty: &'static ImposterType) -> TypeRegistration {
+        TypeRegistration {
+            ty,
+            serialize: Some(serialize_erased::<T>),
+            deserialize: Some(deserialize_erased::<T>),
+        }
+    }
+
+    fn insert(&mut self, name: Box<str>, registration: TypeRegistration) {
+        let type_id = registration.ty.type_id();
+        if let Some(old_name) = self.names.insert(type_id, name.clone()) {
+            self.types.remove(&old_name);
+        }
+
+        if let Some(old) = self.types.insert(name, registration) {
+            if old.ty.type_id() != type_id {
+                self.names.remove(&old.ty.type_id());
+            }
+        }
+    }
+
+    /// Returns the registration for `name` as `Some(&TypeRegistration)`
+    ///
+    /// If `name` is not registered, `None` is returned.
+    #[inline]
+    pub fn get(&self, name: &str) -> Option<&TypeRegistration> {
+        self.types.get(name)
+    }
+
+    /// Returns the [`ImposterType`] registered as `name`
+    ///
+    /// If `name` is not registered, `None` is returned.
+    #[inline]
+    pub fn type_of(&self, name: &str) -> Option<&'static ImposterType> {
+        self.get(name).map(|registration| registration.ty)
+    }
+
+    /// Returns the id of the type registered as `name`
+    ///
+    /// If `name` is not registered, `None` is returned.
+    #[inline]
+    pub fn type_id_of(&self, name: &str) -> Option<ImposterTypeId> {
+        self.type_of(name).map(ImposterType::type_id)
+    }
+
+    /// Returns the name the type identified by `type_id` is registered with
+    ///
+    /// If the type is not registered, `None` is returned.
+    #[inline]
+    pub fn name_of(&self, type_id: impl Into<ImposterTypeId>) -> Option<&str> {
+        self.names.get(&type_id.into()).map(|name| &**name)
+    }
+
+    /// Returns the name the type `T` is registered with
+    ///
+    /// If `T` is not registered, `None` is returned.
+    #[inline]
+    pub fn name_of_type<T: 'static>(&self) -> Option<&str> {
+        self.name_of(ImposterTypeId::of::<T>())
+    }
+
+    /// Returns an iterator over all registered names and their registrations
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &TypeRegistration)> {
+        self.types
+            .iter()
+            .map(|(name, registration)| (&**name, registration))
+    }
+
+    /// Returns the number of registered types
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.types.len()
+    }
+
+    /// Returns `true` if no types are registered
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.types.is_empty()
+    }
+
+    /// Writes the value in `imposter` to `writer`
+    #[inline]
+    pub fn serialize_imposter<A: GlobalAlloc>(
+        &self,
+        imposter: &Imposter<A>,
+        writer: impl Write,
+    ) -> Result<(), SerializeError> {
+        self.serialize_ref(imposter.as_imposter_ref(), writer)
+    }
+
+    /// Writes the referenced value to `writer`
+    pub fn serialize_ref(
+        &self,
+        item: ImposterRef<'_>,
+        mut writer: impl Write,
+    ) -> Result<(), SerializeError> {
+        let (name, serialize) = self.serialize_entry(item.imposter_type())?;
+        write_name(name, &mut writer)?;
+        unsafe { serialize(item.as_ptr(), &mut writer)? };
+        Ok(())
+    }
+
+    /// Writes all items in `vec` to `writer`
+    pub fn serialize_vec<A: GlobalAlloc>(
+        &self,
+        vec: &ImposterVec<A>,
+        mut writer: impl Write,
+    ) -> Result<(), SerializeError> {
+        let (name, serialize) = self.serialize_entry(vec.imposter_type())?;
+        write_name(name, &mut writer)?;
+        write_len(vec.len(), &mut writer)?;
+        for item in vec {
+            unsafe { serialize(item.as_ptr(), &mut writer)? };
+        }
+
+        Ok(())
+    }
+
+    /// Reads an imposter from `reader`
+    pub fn deserialize_imposter(&self, mut reader: impl Read) -> Result<Imposter, SerializeError> {
+        let (ty, deserialize) = self.deserialize_entry(&mut reader)?;
+        let imposter =
+            unsafe { Imposter::try_init_with_in(ty, Global, |dst| deserialize(&mut reader, dst))? };
+        Ok(imposter)
+    }
+
+    /// Reads a vec from `reader`
+    ///
+    /// The returned vec contains items of the type registered with the name found in the data.
+    /// Vecs of a zero sized type are rejected as invalid data if they are longer than [`MAX_ZERO_SIZED_LEN`](crate::MAX_ZERO_SIZED_LEN).
+    pub fn deserialize_vec(&self, mut reader: impl Read) -> Result<ImposterVec, SerializeError> {
+        let (ty, deserialize) = self.deserialize_entry(&mut reader)?;
+        let len = read_items_len(ty.layout().size(), &mut reader)?;
+        let mut vec = ImposterVec::with_type(ty);
+        for _ in 0..len {
+            unsafe {
+                vec.try_push_with(|dst| deserialize(&mut reader, dst).map_err(SerializeError::Io))?
+            };
+        }
+
+        Ok(vec)
+    }
+
+    fn serialize_entry(
+        &self,
+        ty: &'static ImposterType,
+    ) -> Result<(&str, ImposterSerialize), SerializeError> {
+        self.names
+            .get(&ty.type_id())
+            .and_then(|name| Some((&**name, self.types[name].serialize?)))
+            .ok_or(SerializeError::Unregistered(ty.type_name()))
+    }
+
+    fn deserialize_entry(
+        &self,
+        reader: &mut dyn Read,
+    ) -> Result<(&'static ImposterType, ImposterDeserialize), SerializeError> {
+        let name = read_name(reader)?;
+        let registration = self.get(&name).ok_or(SerializeError::UnknownName(name))?;
+        let deserialize = registration
+            .deserialize
+            .ok_or(SerializeError::Unregistered(registration.ty.type_name()))?;
+        Ok((registration.ty, deserialize))
+    }
+}
+
+fn write_name(name: &str, writer: &mut dyn Write) -> Result<(), SerializeError> {
+    write_len(name.len(), writer)?;
+    writer.write_all(name.as_bytes())?;
+    Ok(())
+}
+
+fn read_name(reader: &mut dyn Read) -> Result<String, SerializeError> {
+    let len = read_len(reader)?;
+    let name = String::from_utf8(read_bytes(len, reader)?)
+        .map_err(|_| invalid_data("invalid type name"))?;
+    Ok(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        alloc::Layout,
+        any::TypeId,
+        io::{self, Read, Write},
+        sync::atomic::{AtomicUsize, Ordering},
+    };
+
+    use super::*;
+    use crate::MAX_ZERO_SIZED_LEN;
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Position {
+        x: f32,
+        y: f32,
+    }
+
+    impl Serializable for Position {
+        fn serialize(&self, writer: &mut dyn Write) -> io::Result<()> {
+            self.x.serialize(writer)?;
+            self.y.serialize(writer)
+        }
+
+        fn deserialize(reader: &mut dyn Read) -> io::Result<Self> {
+            let x = f32::deserialize(reader)?;
+            let y = f32::deserialize(reader)?;
+            Ok(Self { x, y })
+        }
+    }
+
+    #[test]
+    fn serialize_vec() {
+        let mut registry = TypeRegistry::new();
+        registry.register::<Position>("position");
+        registry.register::<String>("string");
+
+        let mut vec = ImposterVec::new::<Position>();
+        vec.push_item(Position { x: 1., y: 2. }).unwrap();
+        vec.push_item(Position { x: 3., y: 4. }).unwrap();
+
+        let mut bytes = Vec::new();
+        registry.serialize_vec(&vec, &mut bytes).unwrap();
+        assert_eq!(bytes.len(), 8 + 8 + 8 + 16);
+
+        let read = registry.deserialize_vec(bytes.as_slice()).unwrap();
+        assert_eq!(read.as_slice::<Position>(), vec.as_slice::<Position>());
+
+        let read = registry
+            .deserialize_vec(&bytes[..bytes.len() - 1])
+            .unwrap_err();
+        assert!(matches!(read, SerializeError::Io(_)));
+
+        let vec = ImposterVec::new::<String>();
+        bytes.clear();
+        registry.serialize_vec(&vec, &mut bytes).unwrap();
+        let read = registry.deserialize_vec(bytes.as_slice()).unwrap();
+        assert!(read.is_type::<String>() && read.is_empty());
+    }
+
+    #[test]
+    fn serialize_zero_sized_vec() {
+        let mut registry = TypeRegistry::new();
+        registry.register::<()>("unit");
+
+        let mut vec = ImposterVec::new::<()>();
+        for _ in 0..MAX_ZERO_SIZED_LEN {
+            vec.push_item(()).unwrap();
+        }
+
+        let mut bytes = Vec::new();
+        registry.serialize_vec(&vec, &mut bytes).unwrap();
+        let read = registry.deserialize_vec(bytes.as_slice()).unwrap();
+        assert_eq!(read.len(), MAX_ZERO_SIZED_LEN);
+
+        // longer vecs can be written, but are rejected instead of reading items almost forever
+        vec.push_item(()).unwrap();
+        bytes.clear();
+        registry.serialize_vec(&vec, &mut bytes).unwrap();
+        let error = registry.deserialize_vec(bytes.as_slice()).unwrap_err();
+        assert!(matches!(error, SerializeError::Io(e) if e.kind() == io::ErrorKind::InvalidData));
+    }
+
+    #[test]
+    fn serialize_imposter() {
+        let mut registry = TypeRegistry::new();
+        registry
+            .register_with_type::<String>("string", ImposterType::cloneable::<String>())
+            .unwrap();
+        assert!(registry
+            .register_with_type::<u32>("u32", ImposterType::of::<u64>())
+            .is_err());
+
+        let mut bytes = Vec::new();
+        let imposter = Imposter::new(String::from("sus"));
+        registry.serialize_imposter(&imposter, &mut bytes).unwrap();
+        let read = registry.deserialize_imposter(bytes.as_slice()).unwrap();
+        let clone = read.try_clone().unwrap();
+        assert_eq!(clone.downcast::<String>().unwrap(), "sus");
+
+        let error = registry.serialize_imposter(&Imposter::new(42u32), &mut bytes);
+        assert!(matches!(error, Err(SerializeError::Unregistered(_))));
+    }
+
+    #[test]
+    fn registry_names() {
+        let mut registry = TypeRegistry::new();
+        registry.register::<u32>("int");
+        registry.register::<i64>("int");
+        assert_eq!(registry.len(), 1);
+        assert!(registry.get("int").unwrap().imposter_type().is::<i64>());
+
+        let mut bytes = Vec::new();
+        let error = registry.serialize_imposter(&Imposter::new(42u32), &mut bytes);
+        assert!(matches!(error, Err(SerializeError::Unregistered(_))));
+
+        registry.register::<i64>("long");
+        assert_eq!(registry.len(), 1);
+        assert!(registry.get("int").is_none());
+        registry
+            .serialize_imposter(&Imposter::new(42i64), &mut bytes)
+            .unwrap();
+
+        let registry = TypeRegistry::new();
+        let error = registry.deserialize_imposter(bytes.as_slice()).unwrap_err();
+        assert!(matches!(error, SerializeError::UnknownName(name) if name == "long"));
+    }
+
+    #[test]
+    fn registered_types() {
+        static HEALTH: ImposterType = ImposterType::builder::<u32>().with_default().build();
+        static COLOR: ImposterType =
+            unsafe { ImposterType::runtime(7, "Color", Layout::new::<[u8; 3]>(), None) };
+
+        let mut registry = TypeRegistry::new();
+        registry.register_type("color", &COLOR);
+        registry.register_type("health", &HEALTH);
+        assert_eq!(
+            registry.type_id_of("color"),
+            Some(ImposterTypeId::Runtime(7))
+        );
+        assert_eq!(registry.name_of(ImposterTypeId::Runtime(7)), Some("color"));
+        assert_eq!(registry.name_of(TypeId::of::<u32>()), Some("health"));
+        assert_eq!(registry.name_of_type::<u64>(), None);
+        assert_eq!(registry.iter().count(), 2);
+
+        let health = Imposter::default_for(registry.type_of("health").unwrap()).unwrap();
+        assert_eq!(health.downcast::<u32>().unwrap(), 0);
+        assert!(Imposter::default_for(registry.type_of("color").unwrap()).is_none());
+
+        let mut bytes = Vec::new();
+        let error = registry.serialize_imposter(&Imposter::new(42u32), &mut bytes);
+        assert!(matches!(error, Err(SerializeError::Unregistered("u32"))));
+    }
+
+    #[test]
+    fn deserialize_drop() {
+        static DROPS: AtomicUsize = AtomicUsize::new(0);
+
+        struct Counted(u8);
+
+        impl Drop for Counted {
+            fn drop(&mut self) {
+                DROPS.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+
+        impl Serializable for Counted {
+            fn serialize(&self, writer: &mut dyn Write) -> io::Result<()> {
+                self.0.serialize(writer)
+            }
+
+            fn deserialize(reader: &mut dyn Read) -> io::Result<Self> {
+                u8::deserialize(reader).map(Self)
+            }
+        }
+
+        let mut registry = TypeRegistry::new();
+        registry.register::<Counted>("counted");
+
+        let mut vec = ImposterVec::new::<Counted>();
+        for i in 0..3 {
+            vec.push_item(Counted(i)).unwrap();
+        }
+
+        let mut bytes = Vec::new();
+        registry.serialize_vec(&vec, &mut bytes).unwrap();
+        drop(vec);
+        assert_eq!(DROPS.swap(0, Ordering::Relaxed), 3);
+
+        // the items read before the error are dropped with the partial vec
+        bytes.pop();
+        assert!(registry.deserialize_vec(bytes.as_slice()).is_err());
+        assert_eq!(DROPS.load(Ordering::Relaxed), 2);
+    }
+}
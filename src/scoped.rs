@@ -0,0 +1,279 @@
+use std::{
+    alloc::Layout,
+    fmt,
+    marker::PhantomData,
+    mem::{self, ManuallyDrop},
+};
+
+use crate::{Imposter, ImposterType, ImposterTypeId, Rejected};
+
+/// A type that may be stored in scoped imposters that live for the lifetime `'a`
+///
+/// Scoped types are identified using their [`Scoped::Static`] type,
+/// which allows types that borrow data to be type erased.
+///
+/// Most types can implement this trait using the [`impl_scoped`](crate::impl_scoped) macro.
+///
+/// # Safety
+/// - `Static` must be the same type as `Self` with every lifetime `'a` replaced by `'static`
+/// - there must be no other type implementing `Scoped<'a>` with the same `Static` type
+pub unsafe trait Scoped<'a>: Sized + 'a {
+    type Static: 'static;
+}
+
+/// Implements [`Scoped`] for types that have no lifetimes, or exactly one lifetime
+///
+/// # Example
+/// ```
+/// use imposters::{impl_scoped, ScopedImposter};
+///
+/// struct Position(f32, f32);
+/// struct Frame<'a>(&'a [Position]);
+///
+/// impl_scoped!(Position);
+/// impl_scoped!(Frame<'a>);
+///
+/// let positions = vec![Position(0., 1.)];
+/// let imposter = ScopedImposter::new(Frame(&positions));
+/// let frame = imposter.downcast::<Frame>().unwrap();
+/// assert_eq!(frame.0.len(), 1);
+/// ```
+#[macro_export]
+macro_rules! impl_scoped {
+    ($ty:ident<$lt:lifetime>) => {
+        unsafe impl<$lt> $crate::Scoped<$lt> for $ty<$lt> {
+            type Static = $ty<'static>;
+        }
+    };
+    ($($ty:ty),+ $(,)?) => {
+        $(
+            unsafe impl<'a> $crate::Scoped<'a> for $ty {
+                type Static = $ty;
+            }
+        )+
+    };
+}
+
+impl_scoped!(
+    (),
+    bool,
+    char,
+    u8,
+    u16,
+    u32,
+    u64,
+    u128,
+    usize,
+    i8,
+    i16,
+    i32,
+    i64,
+    i128,
+    isize,
+    f32,
+    f64,
+    String
+);
+
+unsafe impl<'a, T: ?Sized + 'static> Scoped<'a> for &'a T {
+    type Static = &'static T;
+}
+
+unsafe impl<'a, T: ?Sized + 'static> Scoped<'a> for &'a mut T {
+    type Static = &'static mut T;
+}
+
+unsafe impl<'a, T: Scoped<'a>> Scoped<'a> for Option<T> {
+    type Static = Option<T::Static>;
+}
+
+unsafe impl<'a, T: Scoped<'a>> Scoped<'a> for Box<T> {
+    type Static = Box<T::Static>;
+}
+
+unsafe impl<'a, T: Scoped<'a>> Scoped<'a> for Vec<T> {
+    type Static = Vec<T::Static>;
+}
+
+/// A type erased wrapper around data that lives for the lifetime `'a`
+///
+/// Unlike [`Imposter`], this may contain data that borrows from its surroundings.
+/// Any data downcast out of this imposter can never outlive `'a`.
+///
+/// ```compile_fail
+/// use imposters::ScopedImposter;
+///
+/// let imposter;
+/// {
+///     let text = String::from("sus");
+///     imposter = ScopedImposter::new(text.as_str());
+/// }
+/// let text = imposter.downcast::<&str>().unwrap();
+/// ```
+pub struct ScopedImposter<'a> {
+    inner: ManuallyDrop<Imposter>,
+    _scope: PhantomData<fn(&'a ()) -> &'a ()>,
+}
+
+impl Drop for ScopedImposter<'_> {
+    #[inline]
+    fn drop(&mut self) {
+        // SAFETY:
+        // the inner imposter is never used again
+        unsafe { ManuallyDrop::drop(&mut self.inner) }
+    }
+}
+
+impl fmt::Debug for ScopedImposter<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ScopedImposter")
+            .field("type_name", &self.type_name())
+            .field("data", &self.inner.data())
+            .finish()
+    }
+}
+
+impl<'a> ScopedImposter<'a> {
+    /// Creates a new scoped imposter containing `item`
+    #[inline]
+    pub fn new<T: Scoped<'a>>(item: T) -> Self {
+        // SAFETY:
+        // `T` and `T::Static` only differ in lifetimes,
+        // so they share the same layout and drop function
+        let inner = unsafe { Imposter::new_unchecked(item, ImposterType::of::<T::Static>()) };
+        unsafe { Self::from_imposter_unchecked(inner) }
+    }
+
+    /// Wraps an existing `imposter` into a scoped imposter
+    ///
+    /// # Safety
+    /// The data inside `imposter` must be valid for the lifetime `'a`
+    #[inline]
+    pub unsafe fn from_imposter_unchecked(imposter: Imposter) -> Self {
+        Self {
+            inner: ManuallyDrop::new(imposter),
+            _scope: PhantomData,
+        }
+    }
+
+    /// Returns the inner [`Imposter`]
+    ///
+    /// # Safety
+    /// The returned imposter must not be used to access the data after `'a` has ended
+    #[inline]
+    pub unsafe fn into_inner(mut self) -> Imposter {
+        let inner = ManuallyDrop::take(&mut self.inner);
+        mem::forget(self);
+        inner
+    }
+
+    /// Downcasts the data in this imposter to an owned type `T`.
+    ///
+    /// If `T` does not match the internal type, the imposter is returned in `Err`
+    #[inline]
+    pub fn downcast<T: Scoped<'a>>(self) -> Result<T, Rejected<Self>> {
+        if !self.has_type::<T>() {
+            let error = self.inner.imposter_type().mismatch::<T::Static>();
+            return Err(Rejected::new(self, error));
+        }
+
+        // SAFETY:
+        // the type is checked above, and `T` is valid for `'a`
+        Ok(unsafe { self.into_inner().downcast_unchecked() })
+    }
+
+    /// Downcasts the data in this imposter to type `&T`.
+    ///
+    /// If `T` does not match the internal type, `None` is returned.
+    #[inline]
+    pub fn downcast_ref<T: Scoped<'a>>(&self) -> Option<&T> {
+        if !self.has_type::<T>() {
+            return None;
+        }
+
+        // SAFETY:
+        // the type is checked above, and `T` is valid for `'a`
+        Some(unsafe { self.inner.downcast_ref_unchecked() })
+    }
+
+    /// Downcasts the data in this imposter to type `&mut T`.
+    ///
+    /// If `T` does not match the internal type, `None` is returned.
+    #[inline]
+    pub fn downcast_mut<T: Scoped<'a>>(&mut self) -> Option<&mut T> {
+        if !self.has_type::<T>() {
+            return None;
+        }
+
+        // SAFETY:
+        // the type is checked above, and `T` is valid for `'a`
+        Some(unsafe { self.inner.downcast_mut_unchecked() })
+    }
+
+    /// Returns true if `T` matches the internal type
+    #[inline]
+    pub fn has_type<T: Scoped<'a>>(&self) -> bool {
+        self.inner.has_type_id::<T::Static>()
+    }
+
+    /// Returns the [`ImposterType`] describing the internal data
+    ///
+    /// All lifetimes in the described type are replaced with `'static`
+    #[inline]
+    pub fn imposter_type(&self) -> &'static ImposterType {
+        self.inner.imposter_type()
+    }
+
+    /// Returns the type id of the internal data with all lifetimes replaced with `'static`
+    #[inline]
+    pub fn type_id(&self) -> ImposterTypeId {
+        self.inner.type_id()
+    }
+
+    /// Returns the name of the internal type
+    #[inline]
+    pub fn type_name(&self) -> &'static str {
+        self.inner.type_name()
+    }
+
+    /// Returns the layout of the internal type
+    #[inline]
+    pub fn layout(&self) -> Layout {
+        self.inner.layout()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use super::*;
+
+    struct Counter<'a>(&'a Cell<u32>);
+    impl_scoped!(Counter<'a>);
+
+    impl Drop for Counter<'_> {
+        fn drop(&mut self) {
+            self.0.set(self.0.get() + 1);
+        }
+    }
+
+    #[test]
+    fn scoped_imposter() {
+        let text = String::from("sus");
+        let mut imposter = ScopedImposter::new(text.as_str());
+        assert!(imposter.downcast_ref::<&str>().is_some());
+        assert!(imposter.downcast_mut::<&String>().is_none());
+        let imposter = imposter.downcast::<u32>().unwrap_err().into_value();
+        assert_eq!(imposter.downcast::<&str>().unwrap(), "sus");
+    }
+
+    #[test]
+    fn drop_scoped_imposter() {
+        let drops = Cell::new(0);
+        let imposter = ScopedImposter::new(Counter(&drops));
+        assert!(imposter.has_type::<Counter>());
+        drop(imposter);
+        assert_eq!(drops.get(), 1);
+    }
+}
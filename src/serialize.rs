@@ -0,0 +1,300 @@
+use std::io::{self, Read, Write};
+
+/// The maximum length accepted when reading a sequence of zero sized items
+///
+/// Zero sized items may be read without consuming any data or memory,
+/// so a corrupt length would otherwise keep the reader busy almost forever.
+/// Longer sequences can still be written, but reading them fails with [`io::ErrorKind::InvalidData`].
+pub const MAX_ZERO_SIZED_LEN: usize = 1 << 16;
+
+/// Writes the value at `ptr` to `writer`, see [`Serializable::serialize`]
+pub type ImposterSerialize = unsafe fn(ptr: *const u8, writer: &mut dyn Write) -> io::Result<()>;
+
+/// Reads a value from `reader` into the uninitialized memory at `dst`, see [`Serializable::deserialize`]
+///
+/// If an error is returned, `dst` is left uninitialized.
+pub type ImposterDeserialize = unsafe fn(reader: &mut dyn Read, dst: *mut u8) -> io::Result<()>;
+
+/// A type that can be stored using the binary format of a [`TypeRegistry`](crate::TypeRegistry)
+///
+/// Implementations are provided for primitives, strings and collections of serializable types.
+/// Numbers are always written in little endian, and lengths are written as a `u64`.
+pub trait Serializable: Sized {
+    /// Writes this value to `writer`
+    fn serialize(&self, writer: &mut dyn Write) -> io::Result<()>;
+
+    /// Reads a value from `reader`
+    fn deserialize(reader: &mut dyn Read) -> io::Result<Self>;
+}
+
+/// Erased [`Serializable::serialize`] for the type `T`
+///
+/// # Safety
+/// `ptr` must point to a valid value of type `T`
+pub(crate) unsafe fn serialize_erased<T: Serializable>(
+    ptr: *const u8,
+    writer: &mut dyn Write,
+) -> io::Result<()> {
+    (*(ptr as *const T)).serialize(writer)
+}
+
+/// Erased [`Serializable::deserialize`] for the type `T`
+///
+/// # Safety
+/// `dst` must be valid for writes of type `T`
+pub(crate) unsafe fn deserialize_erased<T: Serializable>(
+    reader: &mut dyn Read,
+    dst: *mut u8,
+) -> io::Result<()> {
+    (dst as *mut T).write(T::deserialize(reader)?);
+    Ok(())
+}
+
+/// Writes a length prefix to `writer`
+#[inline]
+pub(crate) fn write_len(len: usize, writer: &mut dyn Write) -> io::Result<()> {
+    (len as u64).serialize(writer)
+}
+
+/// Reads a length prefix from `reader`
+#[inline]
+pub(crate) fn read_len(reader: &mut dyn Read) -> io::Result<usize> {
+    usize::try_from(u64::deserialize(reader)?)
+        .map_err(|_| invalid_data("length does not fit into usize"))
+}
+
+/// Reads the length prefix of a sequence of items that are `item_size` bytes large
+///
+/// Lengths above [`MAX_ZERO_SIZED_LEN`] are rejected for zero sized items.
+#[inline]
+pub(crate) fn read_items_len(item_size: usize, reader: &mut dyn Read) -> io::Result<usize> {
+    let len = read_len(reader)?;
+    if item_size == 0 && len > MAX_ZERO_SIZED_LEN {
+        return Err(invalid_data("too many zero sized items"));
+    }
+
+    Ok(len)
+}
+
+/// Reads exactly `len` bytes from `reader`
+///
+/// The buffer only grows while data is read, so a corrupt length can not cause a huge allocation.
+pub(crate) fn read_bytes(len: usize, reader: &mut dyn Read) -> io::Result<Vec<u8>> {
+    let mut bytes = Vec::new();
+    reader.take(len as u64).read_to_end(&mut bytes)?;
+    if bytes.len() != len {
+        return Err(io::ErrorKind::UnexpectedEof.into());
+    }
+
+    Ok(bytes)
+}
+
+#[inline]
+pub(crate) fn invalid_data(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+macro_rules! serializable_number {
+    ($($ty:ty),*) => {$(
+        impl Serializable for $ty {
+            #[inline]
+            fn serialize(&self, writer: &mut dyn Write) -> io::Result<()> {
+                writer.write_all(&self.to_le_bytes())
+            }
+
+            #[inline]
+            fn deserialize(reader: &mut dyn Read) -> io::Result<Self> {
+                let mut bytes = [0; std::mem::size_of::<$ty>()];
+                reader.read_exact(&mut bytes)?;
+                Ok(Self::from_le_bytes(bytes))
+            }
+        }
+    )*};
+}
+
+serializable_number!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128, f32, f64);
+
+impl Serializable for usize {
+    #[inline]
+    fn serialize(&self, writer: &mut dyn Write) -> io::Result<()> {
+        write_len(*self, writer)
+    }
+
+    #[inline]
+    fn deserialize(reader: &mut dyn Read) -> io::Result<Self> {
+        read_len(reader)
+    }
+}
+
+impl Serializable for isize {
+    #[inline]
+    fn serialize(&self, writer: &mut dyn Write) -> io::Result<()> {
+        (*self as i64).serialize(writer)
+    }
+
+    #[inline]
+    fn deserialize(reader: &mut dyn Read) -> io::Result<Self> {
+        Self::try_from(i64::deserialize(reader)?)
+            .map_err(|_| invalid_data("value does not fit into isize"))
+    }
+}
+
+impl Serializable for bool {
+    #[inline]
+    fn serialize(&self, writer: &mut dyn Write) -> io::Result<()> {
+        (*self as u8).serialize(writer)
+    }
+
+    #[inline]
+    fn deserialize(reader: &mut dyn Read) -> io::Result<Self> {
+        match u8::deserialize(reader)? {
+            0 => Ok(false),
+            1 => Ok(true),
+            _ => Err(invalid_data("invalid bool")),
+        }
+    }
+}
+
+impl Serializable for char {
+    #[inline]
+    fn serialize(&self, writer: &mut dyn Write) -> io::Result<()> {
+        (*self as u32).serialize(writer)
+    }
+
+    #[inline]
+    fn deserialize(reader: &mut dyn Read) -> io::Result<Self> {
+        char::from_u32(u32::deserialize(reader)?).ok_or_else(|| invalid_data("invalid char"))
+    }
+}
+
+impl Serializable for () {
+    #[inline]
+    fn serialize(&self, _writer: &mut dyn Write) -> io::Result<()> {
+        Ok(())
+    }
+
+    #[inline]
+    fn deserialize(_reader: &mut dyn Read) -> io::Result<Self> {
+        Ok(())
+    }
+}
+
+impl Serializable for String {
+    fn serialize(&self, writer: &mut dyn Write) -> io::Result<()> {
+        write_len(self.len(), writer)?;
+        writer.write_all(self.as_bytes())
+    }
+
+    fn deserialize(reader: &mut dyn Read) -> io::Result<Self> {
+        let len = read_len(reader)?;
+        String::from_utf8(read_bytes(len, reader)?).map_err(|_| invalid_data("invalid utf-8"))
+    }
+}
+
+impl<T: Serializable> Serializable for Option<T> {
+    fn serialize(&self, writer: &mut dyn Write) -> io::Result<()> {
+        self.is_some().serialize(writer)?;
+        match self {
+            Some(value) => value.serialize(writer),
+            None => Ok(()),
+        }
+    }
+
+    fn deserialize(reader: &mut dyn Read) -> io::Result<Self> {
+        match bool::deserialize(reader)? {
+            true => Ok(Some(T::deserialize(reader)?)),
+            false => Ok(None),
+        }
+    }
+}
+
+impl<T: Serializable> Serializable for Box<T> {
+    #[inline]
+    fn serialize(&self, writer: &mut dyn Write) -> io::Result<()> {
+        (**self).serialize(writer)
+    }
+
+    #[inline]
+    fn deserialize(reader: &mut dyn Read) -> io::Result<Self> {
+        T::deserialize(reader).map(Box::new)
+    }
+}
+
+impl<T: Serializable> Serializable for Vec<T> {
+    fn serialize(&self, writer: &mut dyn Write) -> io::Result<()> {
+        write_len(self.len(), writer)?;
+        self.iter().try_for_each(|item| item.serialize(writer))
+    }
+
+    fn deserialize(reader: &mut dyn Read) -> io::Result<Self> {
+        // the length is not trusted for preallocation, in case the data is corrupt
+        (0..read_items_len(std::mem::size_of::<T>(), reader)?)
+            .map(|_| T::deserialize(reader))
+            .collect()
+    }
+}
+
+impl<T: Serializable, const N: usize> Serializable for [T; N] {
+    fn serialize(&self, writer: &mut dyn Write) -> io::Result<()> {
+        self.iter().try_for_each(|item| item.serialize(writer))
+    }
+
+    fn deserialize(reader: &mut dyn Read) -> io::Result<Self> {
+        let items = (0..N)
+            .map(|_| T::deserialize(reader))
+            .collect::<io::Result<Vec<T>>>()?;
+        Ok(items
+            .try_into()
+            .unwrap_or_else(|_| unreachable!("exactly N items were read")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn round_trip<T: Serializable>(value: &T) -> T {
+        let mut bytes = Vec::new();
+        value.serialize(&mut bytes).unwrap();
+        let mut reader = bytes.as_slice();
+        let value = T::deserialize(&mut reader).unwrap();
+        assert!(reader.is_empty());
+        value
+    }
+
+    #[test]
+    fn serialize_values() {
+        assert_eq!(round_trip(&-42i32), -42);
+        assert_eq!(round_trip(&1.5f64), 1.5);
+        assert_eq!(round_trip(&usize::MAX), usize::MAX);
+        assert!(round_trip(&true));
+        assert_eq!(round_trip(&'ඞ'), 'ඞ');
+        assert_eq!(round_trip(&String::from("sus")), "sus");
+        assert_eq!(round_trip(&Some([1u8, 2, 3])), Some([1, 2, 3]));
+        assert_eq!(
+            round_trip(&vec![None, Some(Box::new(7u64))]),
+            [None, Some(Box::new(7))]
+        );
+
+        let mut bytes = Vec::new();
+        0x0102u16.serialize(&mut bytes).unwrap();
+        assert_eq!(bytes, [2, 1]);
+    }
+
+    #[test]
+    fn deserialize_invalid() {
+        let error = bool::deserialize(&mut [2u8].as_slice()).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+
+        let error = u32::deserialize(&mut [1u8, 2].as_slice()).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::UnexpectedEof);
+
+        // a huge length with no data behind it
+        let mut bytes = Vec::new();
+        u64::MAX.serialize(&mut bytes).unwrap();
+        assert!(String::deserialize(&mut bytes.as_slice()).is_err());
+        assert!(Vec::<u8>::deserialize(&mut bytes.as_slice()).is_err());
+        let error = Vec::<()>::deserialize(&mut bytes.as_slice()).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+    }
+}
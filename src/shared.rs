@@ -0,0 +1,192 @@
+use std::{
+    fmt,
+    ops::Deref,
+    rc::{self, Rc},
+    sync::{self, Arc},
+};
+
+use crate::{Imposter, SyncImposter};
+
+macro_rules! shared_imposter {
+    (
+        $(#[$meta:meta])*
+        $name:ident($ptr:ident<$inner:ident>),
+        $(#[$weak_meta:meta])*
+        $weak:ident($weak_ptr:path)
+        $(: $($bound:tt)+)?
+    ) => {
+        $(#[$meta])*
+        pub struct $name($ptr<$inner>);
+
+        $(#[$weak_meta])*
+        pub struct $weak($weak_ptr);
+
+        impl fmt::Debug for $name {
+            #[inline]
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                self.0.fmt(f)
+            }
+        }
+
+        impl fmt::Debug for $weak {
+            #[inline]
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str(concat!("(", stringify!($weak), ")"))
+            }
+        }
+
+        impl Clone for $name {
+            #[inline]
+            fn clone(&self) -> Self {
+                Self(self.0.clone())
+            }
+        }
+
+        impl Clone for $weak {
+            #[inline]
+            fn clone(&self) -> Self {
+                Self(self.0.clone())
+            }
+        }
+
+        impl Deref for $name {
+            type Target = $inner;
+
+            #[inline]
+            fn deref(&self) -> &Self::Target {
+                &self.0
+            }
+        }
+
+        impl From<$inner> for $name {
+            #[inline]
+            fn from(imposter: $inner) -> Self {
+                Self($ptr::new(imposter))
+            }
+        }
+
+        impl $name {
+            /// Creates a new shared imposter containing `item`
+            #[inline]
+            pub fn new<T: 'static $(+ $($bound)+)?>(item: T) -> Self {
+                Self($ptr::new($inner::new(item)))
+            }
+
+            /// Downcasts the data in this imposter to type `&T`.
+            ///
+            /// If `T` does not match the internal type, `None` is returned.
+            #[inline]
+            pub fn downcast_ref<T: 'static>(&self) -> Option<&T> {
+                self.0.downcast_ref()
+            }
+
+            /// Creates a new weak handle to this imposter
+            #[inline]
+            pub fn downgrade(&self) -> $weak {
+                $weak($ptr::downgrade(&self.0))
+            }
+
+            /// Returns the inner imposter if this is the only strong handle to it
+            ///
+            /// Otherwise, this handle is returned in `Err`
+            #[inline]
+            pub fn try_unwrap(self) -> Result<$inner, Self> {
+                $ptr::try_unwrap(self.0).map_err(Self)
+            }
+
+            /// Returns the number of strong handles to this imposter
+            #[inline]
+            pub fn strong_count(&self) -> usize {
+                $ptr::strong_count(&self.0)
+            }
+
+            /// Returns the number of weak handles to this imposter
+            #[inline]
+            pub fn weak_count(&self) -> usize {
+                $ptr::weak_count(&self.0)
+            }
+
+            /// Returns true if both handles point to the same imposter
+            #[inline]
+            pub fn ptr_eq(&self, other: &Self) -> bool {
+                $ptr::ptr_eq(&self.0, &other.0)
+            }
+        }
+
+        impl $weak {
+            /// Attempts to upgrade this weak handle into a strong handle
+            ///
+            /// Returns `None` if the imposter has already been dropped
+            #[inline]
+            pub fn upgrade(&self) -> Option<$name> {
+                self.0.upgrade().map($name)
+            }
+
+            /// Returns the number of strong handles to the imposter
+            #[inline]
+            pub fn strong_count(&self) -> usize {
+                self.0.strong_count()
+            }
+
+            /// Returns the number of weak handles to the imposter
+            #[inline]
+            pub fn weak_count(&self) -> usize {
+                self.0.weak_count()
+            }
+        }
+    };
+}
+
+shared_imposter! {
+    /// A reference counted [`Imposter`] with shared ownership
+    RcImposter(Rc<Imposter>),
+    /// A weak handle to an [`RcImposter`]
+    WeakRcImposter(rc::Weak<Imposter>)
+}
+
+shared_imposter! {
+    /// An atomically reference counted [`SyncImposter`] with shared ownership
+    ///
+    /// This may only contain types that are [`Send`] and [`Sync`],
+    /// so it may be freely shared between threads.
+    ArcImposter(Arc<SyncImposter>),
+    /// A weak handle to an [`ArcImposter`]
+    WeakArcImposter(sync::Weak<SyncImposter>): Send + Sync
+}
+
+#[cfg(test)]
+mod tests {
+    use std::thread;
+
+    use super::*;
+
+    #[test]
+    fn rc_imposter() {
+        let imposter = RcImposter::new(String::from("sus"));
+        let shared = imposter.clone();
+        let weak = imposter.downgrade();
+        assert_eq!(imposter.strong_count(), 2);
+        assert_eq!(weak.weak_count(), 1);
+        assert!(imposter.ptr_eq(&shared));
+        assert_eq!(shared.downcast_ref::<String>().unwrap(), "sus");
+
+        let imposter = imposter.try_unwrap().unwrap_err();
+        drop(shared);
+        let imposter = imposter.try_unwrap().unwrap();
+        assert!(weak.upgrade().is_none());
+        assert_eq!(imposter.downcast::<String>().unwrap(), "sus");
+    }
+
+    #[test]
+    fn arc_imposter() {
+        let imposter = ArcImposter::new(42u64);
+        let weak = imposter.downgrade();
+        let value = thread::spawn(move || *weak.upgrade().unwrap().downcast_ref::<u64>().unwrap())
+            .join()
+            .unwrap();
+        assert_eq!(value, 42);
+
+        let imposter = Imposter::from(imposter.try_unwrap().unwrap());
+        assert_eq!(imposter.downcast::<u64>().unwrap(), 42);
+    }
+}
@@ -0,0 +1,182 @@
+use std::{fmt, ops::Deref};
+
+use crate::{Imposter, ImposterType, Rejected};
+
+macro_rules! thread_safe_imposter {
+    ($(#[$meta:meta])* $name:ident: $($bound:tt)+) => {
+        $(#[$meta])*
+        pub struct $name(Imposter);
+
+        impl fmt::Debug for $name {
+            #[inline]
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                self.0.fmt(f)
+            }
+        }
+
+        impl Deref for $name {
+            type Target = Imposter;
+
+            #[inline]
+            fn deref(&self) -> &Self::Target {
+                &self.0
+            }
+        }
+
+        impl From<$name> for Imposter {
+            #[inline]
+            fn from(imposter: $name) -> Self {
+                imposter.0
+            }
+        }
+
+        impl $name {
+            /// Creates a new imposter containing `item`
+            #[inline]
+            pub fn new<T: 'static + $($bound)+>(item: T) -> Self {
+                Self(Imposter::new(item))
+            }
+
+            /// Creates a new imposter containing `item` that can be cloned using [`Imposter::try_clone`]
+            #[inline]
+            pub fn new_cloneable<T: Clone + 'static + $($bound)+>(item: T) -> Self {
+                Self(Imposter::new_cloneable(item))
+            }
+
+            /// Creates a new imposter containing `item` that formats its contents when debugged
+            #[inline]
+            pub fn new_debuggable<T: fmt::Debug + 'static + $($bound)+>(item: T) -> Self {
+                Self(Imposter::new_debuggable(item))
+            }
+
+            /// Creates a new imposter containing `item` that is described by `ty`
+            ///
+            /// If `ty` does not describe `T`, the item is returned in `Err`
+            #[inline]
+            pub fn with_type<T: 'static + $($bound)+>(
+                item: T,
+                ty: &'static ImposterType,
+            ) -> Result<Self, Rejected<T>> {
+                Imposter::with_type(item, ty).map(Self)
+            }
+
+            /// Wraps an existing `imposter`
+            ///
+            /// # Safety
+            #[doc = concat!("The type inside `imposter` must be `", stringify!($($bound)+), "`")]
+            #[inline]
+            pub unsafe fn from_imposter_unchecked(imposter: Imposter) -> Self {
+                Self(imposter)
+            }
+
+            /// Returns the inner [`Imposter`]
+            #[inline]
+            pub fn into_inner(self) -> Imposter {
+                self.0
+            }
+
+            /// Downcasts the data in this imposter to an owned type `T`.
+            ///
+            /// If `T` does not match the internal type, the imposter is returned in `Err`
+            #[inline]
+            pub fn downcast<T: 'static>(self) -> Result<T, Rejected<Self>> {
+                self.0.downcast().map_err(|e| {
+                    let (imposter, error) = e.into_parts();
+                    Rejected::new(Self(imposter), error)
+                })
+            }
+
+            /// Downcasts the data in this imposter to an owned type `Box<T>`.
+            ///
+            /// If `T` does not match the internal type, the imposter is returned in `Err`
+            #[inline]
+            pub fn downcast_box<T: 'static>(self) -> Result<Box<T>, Rejected<Self>> {
+                self.0.downcast_box().map_err(|e| {
+                    let (imposter, error) = e.into_parts();
+                    Rejected::new(Self(imposter), error)
+                })
+            }
+
+            /// Downcasts the data in this imposter to type `&mut T`.
+            ///
+            /// If `T` does not match the internal type, `None` is returned.
+            #[inline]
+            pub fn downcast_mut<T: 'static>(&mut self) -> Option<&mut T> {
+                self.0.downcast_mut()
+            }
+
+            /// Returns a clone of this imposter
+            ///
+            /// Returns `None` if no clone function was recorded for the internal type.
+            #[inline]
+            pub fn try_clone(&self) -> Option<Self> {
+                self.0.try_clone().map(Self)
+            }
+        }
+    };
+}
+
+thread_safe_imposter! {
+    /// An [`Imposter`] that may only contain types that are [`Send`]
+    ///
+    /// Because every value it can hold is `Send`, this imposter can safely be sent to other threads.
+    SendImposter: Send
+}
+
+thread_safe_imposter! {
+    /// An [`Imposter`] that may only contain types that are [`Send`] and [`Sync`]
+    ///
+    /// Because every value it can hold is `Send` and `Sync`,
+    /// this imposter can safely be sent to and shared between other threads.
+    SyncImposter: Send + Sync
+}
+
+// SAFETY:
+// a `SendImposter` can only be created with data that is `Send`
+unsafe impl Send for SendImposter {}
+
+// SAFETY:
+// a `SyncImposter` can only be created with data that is `Send + Sync`
+unsafe impl Send for SyncImposter {}
+unsafe impl Sync for SyncImposter {}
+
+impl From<SyncImposter> for SendImposter {
+    #[inline]
+    fn from(imposter: SyncImposter) -> Self {
+        Self(imposter.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::Arc, thread};
+
+    use super::*;
+
+    #[test]
+    fn send_imposter() {
+        let imposter = SendImposter::new(String::from("sus"));
+        let imposter = thread::spawn(move || {
+            let mut imposter = imposter;
+            imposter
+                .downcast_mut::<String>()
+                .unwrap()
+                .push_str("picious");
+            imposter
+        })
+        .join()
+        .unwrap();
+        assert_eq!(imposter.downcast::<String>().unwrap(), "suspicious");
+    }
+
+    #[test]
+    fn sync_imposter() {
+        let imposter = Arc::new(SyncImposter::new(42u64));
+        let shared = imposter.clone();
+        let value = thread::spawn(move || *shared.downcast_ref::<u64>().unwrap())
+            .join()
+            .unwrap();
+        assert_eq!(value, 42);
+        assert_eq!(imposter.type_name(), "u64");
+    }
+}